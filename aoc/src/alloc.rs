@@ -0,0 +1,131 @@
+use crate::report::Allocs;
+
+/// Whether the runner was built to count allocations.
+pub const COUNTING: bool = cfg!(feature = "count-allocs");
+
+/// Runs `f`, counting what it allocates if the runner was built with the
+/// `count-allocs` feature. The counts are for the whole process, so parts
+/// run at the same time in this one share them.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<Allocs>) {
+    #[cfg(feature = "count-allocs")]
+    {
+        let (out, allocs) = counting::measure(f);
+        (out, Some(allocs))
+    }
+    #[cfg(not(feature = "count-allocs"))]
+    {
+        (f(), None)
+    }
+}
+
+#[cfg(feature = "count-allocs")]
+mod counting {
+    use std::{
+        alloc::{GlobalAlloc, Layout, System},
+        sync::atomic::{AtomicU64, Ordering::Relaxed},
+    };
+
+    use crate::report::Allocs;
+
+    /// The system allocator, keeping count of what goes through it.
+    struct Counting;
+
+    static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+    static BYTES: AtomicU64 = AtomicU64::new(0);
+    static CURRENT: AtomicU64 = AtomicU64::new(0);
+    static PEAK: AtomicU64 = AtomicU64::new(0);
+
+    #[global_allocator]
+    static GLOBAL: Counting = Counting;
+
+    fn allocated(size: usize) {
+        ALLOCATIONS.fetch_add(1, Relaxed);
+        BYTES.fetch_add(size as u64, Relaxed);
+        let current = CURRENT.fetch_add(size as u64, Relaxed) + size as u64;
+        PEAK.fetch_max(current, Relaxed);
+    }
+
+    fn freed(size: usize) {
+        CURRENT.fetch_sub(size as u64, Relaxed);
+    }
+
+    // SAFETY: everything is passed straight on to `System`; the counting
+    // never allocates.
+    unsafe impl GlobalAlloc for Counting {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc(layout);
+            if !ptr.is_null() {
+                allocated(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc_zeroed(layout);
+            if !ptr.is_null() {
+                allocated(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout);
+            freed(layout.size());
+        }
+
+        /// Counted as freeing the old block and allocating the new one,
+        /// which is what growing a `Vec` costs when it can't grow in place.
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            let new = System.realloc(ptr, layout, new_size);
+            if !new.is_null() {
+                freed(layout.size());
+                allocated(new_size);
+            }
+            new
+        }
+    }
+
+    /// What `f` allocated, with the peak counted from what was already on
+    /// the heap when it started.
+    pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Allocs) {
+        let allocations = ALLOCATIONS.load(Relaxed);
+        let bytes = BYTES.load(Relaxed);
+        let base = CURRENT.load(Relaxed);
+        PEAK.store(base, Relaxed);
+
+        let out = f();
+
+        let allocs = Allocs {
+            count: ALLOCATIONS.load(Relaxed) - allocations,
+            bytes: BYTES.load(Relaxed) - bytes,
+            peak: PEAK.load(Relaxed).saturating_sub(base),
+        };
+        (out, allocs)
+    }
+
+    #[cfg(test)]
+    mod test {
+        use std::hint::black_box;
+
+        use super::measure;
+
+        #[test]
+        fn test_measure() {
+            // Other tests allocate at the same time, so only lower bounds
+            // hold.
+            let (_, allocs) = measure(|| black_box(vec![0u8; 1 << 20]));
+            assert!(allocs.count >= 1);
+            assert!(allocs.bytes >= 1 << 20);
+            assert!(allocs.peak >= 1 << 19);
+
+            // Freed again straight away, but still counted.
+            let (_, allocs) = measure(|| {
+                for _ in 0..10 {
+                    black_box(vec![0u8; 100]);
+                }
+            });
+            assert!(allocs.count >= 10);
+            assert!(allocs.bytes >= 1000);
+        }
+    }
+}
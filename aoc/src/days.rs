@@ -0,0 +1,147 @@
+use std::hint::black_box;
+
+use harness::Answer;
+
+/// A part's entry point: takes the input and returns its answer, `None` if
+/// the part isn't solved yet, or an error if the input has no answer it can
+/// give.
+pub type Solution = fn(&str) -> Result<Option<String>, String>;
+
+/// One way of solving a part. Every variant of a part should give the same
+/// answer, which `aoc verify` checks.
+pub struct Variant {
+    pub name: &'static str,
+    pub solve: Solution,
+}
+
+/// A day linked into the runner, with the input it was built with. Each
+/// part lists its variants with the one to run by default first.
+pub struct Day {
+    pub day: u32,
+    pub input: &'static str,
+    pub parts: [&'static [Variant]; 2],
+    /// Parses the input and nothing else, so that can be measured on its
+    /// own. Days that parse a line at a time as they go have none.
+    pub parse: Option<fn(&str)>,
+}
+
+impl Day {
+    pub fn name(&self) -> String {
+        format!("day{:02}", self.day)
+    }
+
+    /// The named variant of `part`, or its default if there's no variant
+    /// with that name.
+    pub fn variant(&self, part: usize, name: Option<&str>) -> &Variant {
+        let variants = self.parts[part - 1];
+        name.and_then(|name| variants.iter().find(|v| v.name == name))
+            .unwrap_or(&variants[0])
+    }
+}
+
+macro_rules! variant {
+    ($name:literal, $solve:expr) => {
+        Variant {
+            name: $name,
+            solve: |i| ($solve)(i).answer(),
+        }
+    };
+}
+
+pub static DAYS: &[Day] = &[
+    Day {
+        day: 1,
+        input: day01::INPUT,
+        parse: None,
+        parts: [
+            &[
+                variant!("default", day01::part1),
+                #[cfg(feature = "parallel")]
+                variant!("parallel", day01::par_part1),
+            ],
+            &[
+                variant!("default", day01::part2),
+                variant!("ends", day01::part2_ends),
+                #[cfg(feature = "parallel")]
+                variant!("parallel", day01::par_part2),
+            ],
+        ],
+    },
+    Day {
+        day: 2,
+        input: day02::INPUT,
+        parse: Some(|i| {
+            let _ = black_box(day02::parse(i));
+        }),
+        parts: [
+            &[
+                variant!("default", |i| day02::parse(i).map(|g| day02::part1(&g))),
+                #[cfg(feature = "parallel")]
+                variant!("parallel", |i| day02::parse(i)
+                    .map(|g| day02::par_part1(&g))),
+            ],
+            &[
+                variant!("default", |i| day02::parse(i).map(|g| day02::part2(&g))),
+                #[cfg(feature = "parallel")]
+                variant!("parallel", |i| day02::parse(i)
+                    .map(|g| day02::par_part2(&g))),
+            ],
+        ],
+    },
+    Day {
+        day: 3,
+        input: day03::INPUT,
+        parse: Some(|i| {
+            black_box(day03::Engine::load(i));
+        }),
+        parts: [
+            &[
+                variant!("default", day03::part1),
+                variant!("stream", day03::stream::part1),
+                variant!("hashed", day03::hashed::part1),
+            ],
+            &[
+                variant!("default", day03::part2),
+                variant!("stream", day03::stream::part2),
+                variant!("hashed", day03::hashed::part2),
+            ],
+        ],
+    },
+    Day {
+        day: 4,
+        input: day04::INPUT,
+        parse: Some(|i| {
+            let _ = black_box(day04::parse(i));
+        }),
+        parts: [
+            &[variant!("default", day04::part1)],
+            &[variant!("default", day04::part2)],
+        ],
+    },
+    Day {
+        day: 5,
+        input: day05::INPUT,
+        parse: Some(|i| {
+            let _ = black_box(day05::parse(i));
+        }),
+        parts: [
+            &[
+                variant!("default", day05::part1),
+                #[cfg(feature = "parallel")]
+                variant!("parallel", day05::par_part1),
+            ],
+            &[variant!("default", day05::part2)],
+        ],
+    },
+    Day {
+        day: 6,
+        input: day06::INPUT,
+        parse: Some(|i| {
+            let _ = black_box(day06::races(i));
+        }),
+        parts: [
+            &[variant!("default", day06::part1)],
+            &[variant!("default", day06::part2)],
+        ],
+    },
+];
@@ -0,0 +1,184 @@
+use std::{fs, io, path::Path};
+
+/// An example input from the puzzle text and the answers it gives, where
+/// the text says.
+#[derive(Debug, Default, PartialEq)]
+pub struct Example {
+    pub input: String,
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+fn decode(html: &str) -> String {
+    let mut text = String::new();
+    let mut rest = html;
+
+    // Drop tags like the `<em>` used to highlight parts of examples.
+    while let Some(start) = rest.find('<') {
+        text.push_str(&rest[..start]);
+        rest = rest[start..]
+            .find('>')
+            .map_or("", |end| &rest[start + end + 1..]);
+    }
+    text.push_str(rest);
+
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+/// The contents of every `open ... close` section of `html`, in order.
+fn sections<'a>(html: &'a str, open: &str, close: &str) -> Vec<&'a str> {
+    let mut found = vec![];
+    let mut rest = html;
+
+    while let Some(start) = rest.find(open) {
+        rest = &rest[start + open.len()..];
+        let end = rest.find(close).unwrap_or(rest.len());
+        found.push(&rest[..end]);
+        rest = &rest[end..];
+    }
+
+    found
+}
+
+/// Reads a saved puzzle page. Each part is an `<article>`; its example is
+/// the first `<pre><code>` block in it and its answer is the last
+/// `<code><em>` in it. Part II often reuses part I's example, so when its
+/// article has no block of its own the answer goes with part I's example.
+pub fn extract(html: &str) -> Vec<Example> {
+    let mut examples: Vec<Example> = vec![];
+
+    for (part, article) in sections(html, "<article", "</article>")
+        .into_iter()
+        .take(2)
+        .enumerate()
+    {
+        let answer = sections(article, "<code><em>", "</em></code>")
+            .last()
+            .map(|a| decode(a));
+        let input = sections(article, "<pre><code>", "</code></pre>")
+            .first()
+            .map(|block| decode(block));
+
+        match input {
+            Some(input) => examples.push(Example {
+                input,
+                ..Default::default()
+            }),
+            None if examples.is_empty() => continue,
+            None => {}
+        }
+
+        let example = examples.last_mut().unwrap();
+        if part == 0 {
+            example.part1 = answer;
+        } else {
+            example.part2 = answer;
+        }
+    }
+
+    examples
+}
+
+/// `part1: <answer>` and `part2: <answer>` lines for the answers that are
+/// known.
+pub fn expected(example: &Example) -> String {
+    let mut out = String::new();
+    for (name, answer) in [("part1", &example.part1), ("part2", &example.part2)] {
+        if let Some(answer) = answer {
+            out += &format!("{name}: {answer}\n");
+        }
+    }
+    out
+}
+
+/// Writes `tests/fixtures/exampleN.txt` and `.expected` files for the day,
+/// where its `harness::fixtures!` test picks them up.
+pub fn write(day_dir: &Path, examples: &[Example]) -> io::Result<()> {
+    let fixtures = day_dir.join("tests/fixtures");
+    fs::create_dir_all(&fixtures)?;
+
+    for (i, example) in examples.iter().enumerate() {
+        let name = format!("example{}", i + 1);
+        fs::write(fixtures.join(format!("{name}.txt")), &example.input)?;
+        fs::write(fixtures.join(format!("{name}.expected")), expected(example))?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::{decode, expected, extract, Example};
+
+    static PAGE: &str = r#"<main>
+<article class="day-desc"><h2>--- Day 1: Trebuchet?! ---</h2>
+<p>For example:</p>
+<pre><code>1abc2
+pqr3stu8vwx
+</code></pre>
+<p>Here, the values are <code>12</code> and <code>38</code>.
+Adding these together produces <code><em>50</em></code>.</p>
+</article>
+<p>Your puzzle answer was <code>55208</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<p>For example:</p>
+<pre><code>two1nine
+<em>a&lt;b</em>
+</code></pre>
+<p>Adding these together produces <code><em>29</em></code>.</p>
+</article>
+</main>"#;
+
+    #[test]
+    fn test_decode() {
+        assert_eq!(decode("<em>a&lt;b</em> &amp;&amp; c"), "a<b && c");
+    }
+
+    #[test]
+    fn test_extract() {
+        assert_eq!(
+            extract(PAGE),
+            [
+                Example {
+                    input: "1abc2\npqr3stu8vwx\n".to_string(),
+                    part1: Some("50".to_string()),
+                    part2: None,
+                },
+                Example {
+                    input: "two1nine\na<b\n".to_string(),
+                    part1: None,
+                    part2: Some("29".to_string()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_extract_shared_example() {
+        let page = PAGE.replace("<pre><code>two1nine\n<em>a&lt;b</em>\n</code></pre>", "");
+        assert_eq!(
+            extract(&page),
+            [Example {
+                input: "1abc2\npqr3stu8vwx\n".to_string(),
+                part1: Some("50".to_string()),
+                part2: Some("29".to_string()),
+            }]
+        );
+
+        let part1_only = &PAGE[..PAGE.find("<article class=\"day-desc\"><h2 id").unwrap()];
+        assert_eq!(extract(part1_only).len(), 1);
+        assert!(extract("<p>No puzzle here</p>").is_empty());
+    }
+
+    #[test]
+    fn test_expected() {
+        let examples = extract(PAGE);
+        assert_eq!(expected(&examples[0]), "part1: 50\n");
+        assert_eq!(expected(&examples[1]), "part2: 29\n");
+        assert_eq!(expected(&Example::default()), "");
+    }
+}
@@ -0,0 +1,185 @@
+use std::{collections::BTreeMap, str::FromStr};
+
+/// `--name value` options, each taken at most once so any left over can be
+/// reported as unknown.
+struct Options(BTreeMap<String, String>);
+
+impl Options {
+    fn parse(args: &[String]) -> Result<Self, String> {
+        let mut options = BTreeMap::new();
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            let Some(name) = arg.strip_prefix("--") else {
+                return Err(format!("Expected an option like --seed, got {arg}"));
+            };
+            let Some(value) = args.next() else {
+                return Err(format!("--{name} needs a value"));
+            };
+            options.insert(name.to_string(), value.clone());
+        }
+        Ok(Options(options))
+    }
+
+    fn take<T: FromStr>(&mut self, name: &str, default: T) -> Result<T, String> {
+        match self.0.remove(name) {
+            Some(value) => value
+                .parse()
+                .map_err(|_| format!("Invalid --{name}: {value}")),
+            None => Ok(default),
+        }
+    }
+
+    /// As `take`, for a percentage from 0 to 100.
+    fn percent(&mut self, name: &str, default: u32) -> Result<u32, String> {
+        match self.take(name, default)? {
+            percent if percent <= 100 => Ok(percent),
+            percent => Err(format!("--{name} is a percentage, not {percent}")),
+        }
+    }
+}
+
+/// Made-up input for `day` from `--seed`, `--size` and the day's own
+/// options, always the same for the same arguments. What `--size` counts
+/// depends on the day: lines, games, rows and columns, cards, or lines in
+/// each map.
+pub fn generate(day: &str, args: &[String]) -> Result<String, String> {
+    let mut options = Options::parse(args)?;
+    let seed = options.take("seed", 0)?;
+
+    let input = match day {
+        "day01" => {
+            use day01::generator::{generate, Params};
+            let d = Params::default();
+            let params = Params {
+                overlap: options.take("overlap", d.overlap)?,
+            };
+            generate(seed, options.take("size", 1000)?, &params)
+        }
+        "day02" => {
+            use day02::generator::{generate, Params};
+            let d = Params::default();
+            let params = Params {
+                rounds: options.take("rounds", d.rounds)?,
+                colours: options.take("colours", d.colours)?,
+                cubes: options.take("cubes", d.cubes)?,
+            };
+            generate(seed, options.take("size", 100)?, &params)?
+        }
+        "day03" => {
+            use day03::generator::{generate, Params};
+            let d = Params::default();
+            let params = Params {
+                density: options.percent("density", d.density)?,
+                symbols: options.percent("symbols", d.symbols)?,
+            };
+            if params.density + params.symbols > 100 {
+                return Err("--density and --symbols add up to more than 100".to_string());
+            }
+            generate(seed, options.take("size", 140)?, &params)
+        }
+        "day04" => {
+            use day04::generator::{generate, Params};
+            let d = Params::default();
+            let params = Params {
+                wins: options.take("wins", d.wins)?,
+            };
+            generate(seed, options.take("size", 200)?, &params)
+        }
+        "day05" => {
+            use day05::generator::{generate, Params};
+            let d = Params::default();
+            let params = Params {
+                maps: options.take("maps", d.maps)?,
+                seeds: options.take("seeds", d.seeds)?,
+                width: options.take("width", d.width)?,
+            };
+            generate(seed, options.take("size", 30)?, &params)?
+        }
+        _ => return Err(format!("No generator for {day}")),
+    };
+
+    match options.0.keys().next() {
+        Some(name) => Err(format!("{day} has no --{name} option")),
+        None => Ok(input),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::generate;
+    use crate::days::DAYS;
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn test_generate() {
+        let input = generate("day02", &args("--seed 4 --size 3 --rounds 1")).unwrap();
+        assert_eq!(input.lines().count(), 3);
+        assert!(!input.contains(';'));
+        assert_eq!(
+            generate("day02", &args("--size 3 --rounds 1 --seed 4")),
+            Ok(input)
+        );
+
+        assert_ne!(
+            generate("day03", &args("--seed 1 --size 20")),
+            generate("day03", &args("--seed 2 --size 20"))
+        );
+    }
+
+    #[test]
+    fn test_generated_inputs_solve() {
+        for day in DAYS.iter().filter(|day| day.day <= 5) {
+            let input = generate(&day.name(), &args("--seed 2023 --size 50")).unwrap();
+            let answer = (day.variant(1, None).solve)(&input);
+            assert!(matches!(answer, Ok(Some(_))), "{}", day.name());
+        }
+
+        // Bigger than the puzzle's deck, which part II once assumed.
+        let day04 = DAYS.iter().find(|day| day.day == 4).unwrap();
+        let input = generate("day04", &args("--seed 2023 --size 250")).unwrap();
+        for part in 1..=2 {
+            let answer = (day04.variant(part, None).solve)(&input);
+            assert!(matches!(answer, Ok(Some(_))), "part {part}");
+        }
+    }
+
+    #[test]
+    fn test_generate_errors() {
+        assert_eq!(
+            generate("day06", &[]),
+            Err("No generator for day06".to_string())
+        );
+        assert_eq!(
+            generate("day01", &args("--seed 1 --rounds 3")),
+            Err("day01 has no --rounds option".to_string())
+        );
+        assert_eq!(
+            generate("day04", &args("--wins lots")),
+            Err("Invalid --wins: lots".to_string())
+        );
+        assert!(generate("day04", &args("--size")).is_err());
+        assert!(generate("day04", &args("50")).is_err());
+
+        assert_eq!(
+            generate("day03", &args("--density 1 --symbols 4294967295 --size 3")),
+            Err("--symbols is a percentage, not 4294967295".to_string())
+        );
+        assert_eq!(
+            generate("day03", &args("--density 60 --symbols 50")),
+            Err("--density and --symbols add up to more than 100".to_string())
+        );
+        assert!(generate("day03", &args("--density 60 --symbols 40 --size 3")).is_ok());
+
+        assert_eq!(
+            generate("day02", &args("--size 4294967296")),
+            Err("4294967296 games are too many to number".to_string())
+        );
+        assert_eq!(
+            generate("day05", &args("--size 4 --width 4611686018427387904")),
+            Err("4 lines up to 4611686018427387904 wide won't fit in a u64".to_string())
+        );
+    }
+}
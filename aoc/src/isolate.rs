@@ -0,0 +1,335 @@
+use std::{
+    env,
+    io::{self, Read, Write},
+    process::{Command, ExitStatus, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    days::{Day, Variant, DAYS},
+    report::{self, Format, Record, Status},
+    run_on,
+};
+
+/// The hidden subcommand a child process is started with.
+pub static CHILD: &str = "__run";
+
+/// Limits on a part run in a child process of its own.
+#[derive(Clone, Debug)]
+pub struct Limits {
+    pub timeout: Duration,
+    /// Most address space the child may map, in bytes. Only enforced on
+    /// unix.
+    pub memory: u64,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Limits {
+            timeout: Duration::from_secs(60),
+            memory: 4 << 30,
+        }
+    }
+}
+
+/// How a child process ended.
+#[derive(Debug)]
+enum Exit {
+    Finished {
+        status: ExitStatus,
+        stdout: String,
+        stderr: String,
+    },
+    TimedOut,
+}
+
+fn drain(mut pipe: impl Read + Send + 'static) -> thread::JoinHandle<String> {
+    thread::spawn(move || {
+        let mut out = String::new();
+        let _ = pipe.read_to_string(&mut out);
+        out
+    })
+}
+
+/// Runs `command` with `input` on its stdin, killing it if it runs past
+/// `timeout`, and returns how it ended and how long that took.
+fn supervise(mut command: Command, input: &str, timeout: Duration) -> io::Result<(Exit, Duration)> {
+    let start = Instant::now();
+    let mut child = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    let stdout = drain(child.stdout.take().unwrap());
+    let stderr = drain(child.stderr.take().unwrap());
+    // A child that dies before reading all its input is reported below,
+    // so a broken pipe here doesn't matter.
+    let _ = child.stdin.take().unwrap().write_all(input.as_bytes());
+
+    let mut pause = Duration::from_micros(100);
+    loop {
+        if let Some(status) = child.try_wait()? {
+            let exit = Exit::Finished {
+                status,
+                stdout: stdout.join().unwrap_or_default(),
+                stderr: stderr.join().unwrap_or_default(),
+            };
+            return Ok((exit, start.elapsed()));
+        }
+        if start.elapsed() >= timeout {
+            child.kill()?;
+            child.wait()?;
+            return Ok((Exit::TimedOut, start.elapsed()));
+        }
+        thread::sleep(pause);
+        pause = (pause * 2).min(Duration::from_millis(10));
+    }
+}
+
+/// Killed outright. The runner only does that once a child's time is up,
+/// so otherwise it's likely the kernel, which on Linux usually means the
+/// OOM killer.
+#[cfg(unix)]
+fn killed(status: &ExitStatus) -> bool {
+    use std::os::unix::process::ExitStatusExt;
+    status.signal() == Some(9)
+}
+
+#[cfg(not(unix))]
+fn killed(_: &ExitStatus) -> bool {
+    false
+}
+
+fn failed(day: u32, part: usize, status: Status, error: String, elapsed: Duration) -> Record {
+    Record {
+        day,
+        part,
+        status,
+        answer: None,
+        elapsed,
+        error: Some(error),
+        allocs: None,
+        parse: None,
+    }
+}
+
+/// The record for a part from how its child ended. A child that finished
+/// prints its own record, and has failed if it exits cleanly without one
+/// that can be read. Otherwise it ran out of memory if the allocator said
+/// so, ran out of time if it was killed once its time was up, probably ran
+/// out of memory if it was killed before, and crashed some other way if
+/// not.
+fn classify(day: u32, part: usize, exit: Exit, elapsed: Duration, limits: &Limits) -> Record {
+    let failed = |status, error| failed(day, part, status, error, elapsed);
+    let timed_out = || {
+        failed(
+            Status::Timeout,
+            format!("timed out after {:?}", limits.timeout),
+        )
+    };
+
+    match exit {
+        Exit::TimedOut => timed_out(),
+        Exit::Finished {
+            status,
+            stdout,
+            stderr,
+        } => {
+            if let Some(record) = report::parse_csv(&stdout).pop() {
+                return record;
+            }
+            if status.success() {
+                return failed(
+                    Status::Error,
+                    "finished without a record that makes sense".to_string(),
+                );
+            }
+            if stderr.contains("memory allocation of") {
+                return failed(
+                    Status::OutOfMemory,
+                    format!("ran out of memory, capped at {}MB", limits.memory >> 20),
+                );
+            }
+            if killed(&status) {
+                if elapsed >= limits.timeout {
+                    return timed_out();
+                }
+                return failed(
+                    Status::OutOfMemory,
+                    "killed (possibly out of memory)".to_string(),
+                );
+            }
+            let reason = stderr
+                .lines()
+                .last()
+                .map_or(status.to_string(), str::to_string);
+            failed(Status::Error, format!("crashed: {reason}"))
+        }
+    }
+}
+
+/// Runs one variant of a part on `input` in a child process, so that not
+/// even an abort or a hang can end the whole run.
+pub fn run(day: &Day, part: usize, variant: &Variant, input: &str, limits: &Limits) -> Record {
+    let mut command = Command::new(env::current_exe().expect("can't find the runner's own path"));
+    command.args([
+        CHILD,
+        &day.name(),
+        &part.to_string(),
+        variant.name,
+        &limits.memory.to_string(),
+    ]);
+
+    match supervise(command, input, limits.timeout) {
+        Ok((exit, elapsed)) => classify(day.day, part, exit, elapsed, limits),
+        Err(e) => failed(
+            day.day,
+            part,
+            Status::Error,
+            format!("can't run a child process: {e}"),
+            Duration::ZERO,
+        ),
+    }
+}
+
+#[cfg(unix)]
+fn cap_memory(bytes: u64) {
+    let limit = libc::rlimit {
+        rlim_cur: bytes as libc::rlim_t,
+        rlim_max: bytes as libc::rlim_t,
+    };
+    // SAFETY: `setrlimit` only reads the limit it's given.
+    unsafe { libc::setrlimit(libc::RLIMIT_AS, &limit) };
+}
+
+#[cfg(not(unix))]
+fn cap_memory(_: u64) {}
+
+/// The child's side of `run`: `<day> <part> <variant> <memory>`, with the
+/// input on stdin. Prints the part's record as CSV for the parent to read.
+pub fn child(args: &[String]) -> Result<(), String> {
+    let [day, part, variant, memory] = args else {
+        return Err(format!(
+            "usage: aoc {CHILD} <day> <part> <variant> <memory>"
+        ));
+    };
+    let day = DAYS
+        .iter()
+        .find(|d| d.name() == *day)
+        .ok_or_else(|| format!("No day called {day}"))?;
+    let part: usize = part
+        .parse()
+        .ok()
+        .filter(|p| (1..=2).contains(p))
+        .ok_or_else(|| format!("No part {part}"))?;
+    let memory = memory
+        .parse()
+        .map_err(|_| format!("Invalid memory cap: {memory}"))?;
+
+    let mut input = String::new();
+    io::stdin()
+        .read_to_string(&mut input)
+        .map_err(|e| format!("Can't read the input: {e}"))?;
+
+    cap_memory(memory);
+    let mut record = run_on(day, part, day.variant(part, Some(variant)), &input);
+    // The parent reads records a line at a time.
+    record.error = record.error.map(|e| e.replace(['\n', '\r'], " "));
+    print!("{}", report::render(&[record], Format::Csv));
+    Ok(())
+}
+
+#[cfg(all(test, unix))]
+mod test {
+    use std::{
+        os::unix::process::ExitStatusExt,
+        process::{Command, ExitStatus},
+        time::Duration,
+    };
+
+    use super::{classify, supervise, Exit, Limits};
+    use crate::report::Status;
+
+    fn sh(script: &str) -> Command {
+        let mut command = Command::new("sh");
+        command.args(["-c", script]);
+        command
+    }
+
+    #[test]
+    fn test_supervise() {
+        let (exit, _) = supervise(sh("tr a-z A-Z"), "day05", Duration::from_secs(10)).unwrap();
+        let Exit::Finished { status, stdout, .. } = exit else {
+            panic!("timed out");
+        };
+        assert!(status.success());
+        assert_eq!(stdout, "DAY05");
+
+        let (exit, elapsed) = supervise(sh("sleep 10"), "", Duration::from_millis(50)).unwrap();
+        assert!(matches!(exit, Exit::TimedOut));
+        assert!(elapsed < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_classify() {
+        let limits = Limits {
+            timeout: Duration::from_secs(2),
+            memory: 64 << 20,
+        };
+        let finished = |code: i32, stdout: &str, stderr: &str| Exit::Finished {
+            status: ExitStatus::from_raw(code),
+            stdout: stdout.to_string(),
+            stderr: stderr.to_string(),
+        };
+        let classify = |exit| classify(4, 2, exit, Duration::from_millis(3), &limits);
+
+        let csv = "day,part,status,answer,elapsed_ms,error\n4,2,panic,,1.000,oops\n";
+        let record = classify(finished(0, csv, ""));
+        assert_eq!(
+            (record.status, record.error.as_deref()),
+            (Status::Panic, Some("oops"))
+        );
+        assert_eq!(record.elapsed, Duration::from_millis(1));
+
+        // A record that can't be read back is a failure, not a panic.
+        let csv = "day,part,status,answer,elapsed_ms,error\n4,2,ok,1,NaN,\n";
+        let record = classify(finished(0, csv, ""));
+        assert_eq!(record.status, Status::Error);
+        assert_eq!(
+            record.error.as_deref(),
+            Some("finished without a record that makes sense")
+        );
+
+        let record = classify(Exit::TimedOut);
+        assert_eq!(record.status, Status::Timeout);
+        assert_eq!(record.error.as_deref(), Some("timed out after 2s"));
+
+        // Aborted by SIGABRT after a failed allocation.
+        let oom = "memory allocation of 1048576 bytes failed\n";
+        let record = classify(finished(6, "", oom));
+        assert_eq!(record.status, Status::OutOfMemory);
+        assert_eq!(
+            record.error.as_deref(),
+            Some("ran out of memory, capped at 64MB")
+        );
+
+        // SIGKILLed before the time was up, or as it ran out.
+        let record = classify(finished(9, "", ""));
+        assert_eq!(record.status, Status::OutOfMemory);
+        assert_eq!(
+            record.error.as_deref(),
+            Some("killed (possibly out of memory)")
+        );
+        let late = super::classify(4, 2, finished(9, "", ""), Duration::from_secs(2), &limits);
+        assert_eq!(late.status, Status::Timeout);
+
+        let record = classify(finished(11, "", "thread overflowed its stack\n"));
+        assert_eq!(record.status, Status::Error);
+        assert_eq!(
+            record.error.as_deref(),
+            Some("crashed: thread overflowed its stack")
+        );
+    }
+}
@@ -0,0 +1,444 @@
+mod alloc;
+mod days;
+mod examples;
+mod gen;
+mod isolate;
+mod readme;
+mod report;
+mod shrink;
+mod verify;
+mod watch;
+
+use std::{
+    any::Any,
+    env,
+    fs::{self, OpenOptions},
+    io::Write,
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use days::{Day, Variant, DAYS};
+use isolate::Limits;
+use report::{Format, Parse, Record};
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "panicked".to_string())
+}
+
+/// Runs one variant of a part on the day's input, with a panic reported
+/// rather than ending the whole run.
+fn run(day: &Day, part: usize, variant: &Variant) -> Record {
+    run_on(day, part, variant, day.input)
+}
+
+/// Times the day's parser on its own and counts what it allocates, if
+/// allocations are being counted at all. A parser that panics is left for
+/// the part to report.
+fn run_parse(day: &Day, input: &str) -> Option<Parse> {
+    let parse = day.parse.filter(|_| alloc::COUNTING)?;
+    let start = Instant::now();
+    let (result, allocs) = alloc::measure(|| panic::catch_unwind(|| parse(input)));
+    let elapsed = start.elapsed();
+
+    result.ok()?;
+    Some(Parse {
+        elapsed,
+        allocs: allocs?,
+    })
+}
+
+/// As `run`, on some other input.
+fn run_on(day: &Day, part: usize, variant: &Variant, input: &str) -> Record {
+    let parse = run_parse(day, input);
+    let start = Instant::now();
+    let (result, allocs) =
+        alloc::measure(|| panic::catch_unwind(AssertUnwindSafe(|| (variant.solve)(input))));
+    let elapsed = start.elapsed();
+
+    let record = match result {
+        Ok(Ok(answer)) => report::record(day.day, part, Ok(answer), elapsed),
+        Ok(Err(error)) => report::error(day.day, part, error, elapsed),
+        Err(payload) => report::record(day.day, part, Err(panic_message(payload)), elapsed),
+    };
+    Record {
+        allocs,
+        parse,
+        ..record
+    }
+}
+
+fn parts<'a>(days: &[&'a Day]) -> Vec<(&'a Day, usize)> {
+    days.iter()
+        .flat_map(|day| (1..=2).map(move |part| (*day, part)))
+        .collect()
+}
+
+/// Runs a part in a child process within `limits`, or in this one if
+/// there are none.
+fn run_part(day: &Day, part: usize, variant: Option<&str>, limits: Option<&Limits>) -> Record {
+    let variant = day.variant(part, variant);
+    match limits {
+        Some(limits) => isolate::run(day, part, variant, day.input, limits),
+        None => run(day, part, variant),
+    }
+}
+
+#[cfg(not(feature = "parallel"))]
+fn run_all(days: &[&Day], variant: Option<&str>, limits: Option<&Limits>) -> Vec<Record> {
+    parts(days)
+        .into_iter()
+        .map(|(day, part)| run_part(day, part, variant, limits))
+        .collect()
+}
+
+/// Parts share nothing, so they can all run at once; results still come
+/// back in day order.
+#[cfg(feature = "parallel")]
+fn run_all(days: &[&Day], variant: Option<&str>, limits: Option<&Limits>) -> Vec<Record> {
+    parts(days)
+        .into_par_iter()
+        .map(|(day, part)| run_part(day, part, variant, limits))
+        .collect()
+}
+
+/// The workspace this runner was built from.
+fn root() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap()
+}
+
+fn fail(message: &str) -> ! {
+    eprintln!("{message}");
+    std::process::exit(2);
+}
+
+/// `--timeout`'s value, in seconds.
+fn timeout(seconds: Option<&str>) -> Duration {
+    seconds
+        .and_then(|s| s.parse().ok())
+        .and_then(|s| Duration::try_from_secs_f64(s).ok())
+        .unwrap_or_else(|| fail("--timeout needs a number of seconds"))
+}
+
+/// `--memory`'s value, in megabytes, as bytes.
+fn memory(megabytes: Option<&str>) -> u64 {
+    let megabytes: u64 = megabytes
+        .and_then(|s| s.parse().ok())
+        .unwrap_or_else(|| fail("--memory needs a number of megabytes"));
+    megabytes
+        .checked_mul(1 << 20)
+        .unwrap_or_else(|| fail(&format!("--memory {megabytes} is too many bytes to count")))
+}
+
+/// `aoc shrink <day> [input] [--part N] [--name NAME] [--timeout SECONDS]
+/// [--memory MB] [--in-process]`: cuts an input the day fails on down to a
+/// small one that fails the same way, and saves it as a new fixture for its
+/// answers to be filled in. The input defaults to the day's own. Each
+/// candidate runs in a child process within the limits unless
+/// `--in-process` is given.
+fn shrink(args: &[String]) {
+    const USAGE: &str = "usage: aoc shrink <day> [input] [--part N] [--name NAME] \
+                         [--timeout SECONDS] [--memory MB] [--in-process]";
+
+    let Some(day) = args
+        .first()
+        .and_then(|name| DAYS.iter().find(|d| d.name() == *name))
+    else {
+        fail(USAGE);
+    };
+    let mut path = None;
+    let mut parts = vec![1, 2];
+    let mut name = "shrunk".to_string();
+    let mut limits = Limits::default();
+    let mut in_process = false;
+    let mut args = args[1..].iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--part" => match args.next().map(String::as_str) {
+                Some("1") => parts = vec![1],
+                Some("2") => parts = vec![2],
+                _ => fail(USAGE),
+            },
+            "--name" => name = args.next().cloned().unwrap_or_else(|| fail(USAGE)),
+            "--timeout" => limits.timeout = timeout(args.next().map(String::as_str)),
+            "--memory" => limits.memory = memory(args.next().map(String::as_str)),
+            "--in-process" => in_process = true,
+            _ => path = Some(PathBuf::from(arg)),
+        }
+    }
+
+    // Checked before shrinking, which can take a while, and again when
+    // writing.
+    let fixtures = root().join(day.name()).join("tests/fixtures");
+    let fixture = fixtures.join(format!("{name}.txt"));
+    let expected = fixtures.join(format!("{name}.expected"));
+    for path in [&fixture, &expected] {
+        if path.exists() {
+            fail(&format!(
+                "{} already exists; pick another --name",
+                path.display()
+            ));
+        }
+    }
+
+    let input = match &path {
+        Some(path) => fs::read_to_string(path)
+            .unwrap_or_else(|e| fail(&format!("Can't read {}: {e}", path.display()))),
+        None => day.input.to_string(),
+    };
+
+    // Panics are what's being looked for, so don't also print them.
+    panic::set_hook(Box::new(|_| {}));
+
+    let limits = (!in_process).then_some(&limits);
+    let Some(wanted) = shrink::failure(day, &parts, &input, limits) else {
+        fail(&format!("{} doesn't fail on that input", day.name()));
+    };
+    println!("Failure: {wanted}");
+    let shrunk = shrink::shrink(&input, |candidate| {
+        shrink::failure(day, &parts, candidate, limits).as_ref() == Some(&wanted)
+    });
+    println!(
+        "Shrunk {} lines to {}",
+        input.lines().count(),
+        shrunk.lines().count()
+    );
+
+    fs::create_dir_all(&fixtures)
+        .and_then(|_| {
+            OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(&fixture)
+        })
+        .and_then(|mut file| file.write_all(shrunk.as_bytes()))
+        .unwrap_or_else(|e| fail(&format!("Can't write {}: {e}", fixture.display())));
+
+    // Only the right answers make a test of it, and the harness skips a
+    // fixture without them.
+    println!("Wrote {}", fixture.display());
+    println!(
+        "Next, write the right answers to {}, one line for each part that has one:",
+        expected.display()
+    );
+    for part in &parts {
+        println!("  part{part}: <answer>, or part{part}: error: <message> if it has none");
+    }
+    println!("then run: cargo test -p {}", day.name());
+}
+
+fn main() {
+    let exe = env::current_exe().expect("can't find the runner's own path");
+    let cache = exe.with_file_name("aoc-results.csv");
+    let mut days: Vec<&Day> = DAYS.iter().collect();
+
+    let mut args: Vec<String> = env::args().skip(1).collect();
+
+    if args.first().is_some_and(|a| a == isolate::CHILD) {
+        panic::set_hook(Box::new(|_| {}));
+        isolate::child(&args[1..]).unwrap_or_else(|e| fail(&e));
+        return;
+    }
+
+    if args.first().is_some_and(|a| a == "examples") {
+        let Some(day) = args.get(1) else {
+            fail("usage: aoc examples <day> [puzzle.html]");
+        };
+        let day_dir = root().join(day);
+        let page = args
+            .get(2)
+            .map(PathBuf::from)
+            .unwrap_or_else(|| day_dir.join("puzzle.html"));
+        let html = fs::read_to_string(&page)
+            .unwrap_or_else(|e| fail(&format!("Can't read {}: {e}", page.display())));
+        let found = examples::extract(&html);
+        if found.is_empty() {
+            fail(&format!("No examples found in {}", page.display()));
+        }
+        examples::write(&day_dir, &found)
+            .unwrap_or_else(|e| fail(&format!("Can't write examples: {e}")));
+        println!("Examples for {day}: {}", found.len());
+        return;
+    }
+
+    if args.first().is_some_and(|a| a == "gen") {
+        let Some(day) = args.get(1) else {
+            fail("usage: aoc gen <day> [--seed N] [--size N] [--option value]...");
+        };
+        match gen::generate(day, &args[2..]) {
+            Ok(input) => print!("{input}"),
+            Err(e) => fail(&e),
+        }
+        return;
+    }
+
+    if args.first().is_some_and(|a| a == "watch") {
+        match args.get(1) {
+            Some(day) if DAYS.iter().any(|d| d.name() == *day) => watch::watch(root(), day),
+            _ => fail("usage: aoc watch <day>"),
+        }
+    }
+
+    if args.first().is_some_and(|a| a == "shrink") {
+        shrink(&args[1..]);
+        return;
+    }
+
+    let readme = args.first().is_some_and(|a| a == "readme");
+    let verify = args.first().is_some_and(|a| a == "verify");
+    if readme || verify {
+        args.remove(0);
+    }
+
+    let mut format = Format::Text;
+    let mut cached = false;
+    let mut variant: Option<String> = None;
+    let mut limits = Limits::default();
+    let mut in_process = false;
+    let mut wanted: Vec<String> = vec![];
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--format" => {
+                format = args
+                    .next()
+                    .unwrap_or_default()
+                    .parse()
+                    .unwrap_or_else(|e: String| fail(&e))
+            }
+            "--cached" => cached = true,
+            "--variant" => variant = args.next(),
+            "--timeout" => limits.timeout = timeout(args.next().as_deref()),
+            "--memory" => limits.memory = memory(args.next().as_deref()),
+            "--in-process" => in_process = true,
+            _ => wanted.push(arg),
+        }
+    }
+
+    if !wanted.is_empty() {
+        days.retain(|day| wanted.contains(&day.name()));
+    }
+
+    if let Some(name) = &variant {
+        let known = days
+            .iter()
+            .flat_map(|day| day.parts.iter().copied().flatten())
+            .any(|v| v.name == name);
+        if !known {
+            fail(&format!("No part has a variant called {name}"));
+        }
+    }
+
+    // Panics are reported as errors, so don't also print them.
+    panic::set_hook(Box::new(|_| {}));
+
+    if verify {
+        let (table, agreed) = verify::verify(&days);
+        print!("{table}");
+        if !agreed {
+            fail("Variants disagree");
+        }
+        return;
+    }
+
+    let mut records = if cached {
+        let csv = fs::read_to_string(&cache)
+            .unwrap_or_else(|_| fail("No cached results, run without --cached first"));
+        report::parse_csv(&csv)
+    } else {
+        if days.is_empty() {
+            fail("No days match, expected names like day05");
+        }
+        let records = run_all(&days, variant.as_deref(), (!in_process).then_some(&limits));
+        // Only a cache, so losing it isn't worth failing the run over.
+        let _ = fs::write(&cache, report::render(&records, Format::Csv));
+        records
+    };
+
+    if !wanted.is_empty() {
+        records.retain(|r| wanted.contains(&format!("day{:02}", r.day)));
+    }
+
+    if readme {
+        let path = root().join("README.md");
+        let current = fs::read_to_string(&path).unwrap_or_default();
+        let table = readme::table(&records, |day| readme::title(root(), day));
+        fs::write(&path, readme::update(&current, &table))
+            .unwrap_or_else(|e| fail(&format!("Can't write {}: {e}", path.display())));
+        println!("Updated {}", path.display());
+    } else {
+        print!("{}", report::render(&records, format));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        days::{Day, Variant},
+        report::Status,
+        run, run_on,
+    };
+
+    #[test]
+    fn test_run() {
+        let day = Day {
+            day: 7,
+            input: "1 2",
+            parse: Some(|i| assert!(i.contains(' '))),
+            parts: [
+                &[
+                    Variant {
+                        name: "default",
+                        solve: |i| Ok(Some(i.replace(' ', ""))),
+                    },
+                    Variant {
+                        name: "sum",
+                        solve: |_| Ok(Some("3".to_string())),
+                    },
+                ],
+                &[
+                    Variant {
+                        name: "default",
+                        solve: |_| panic!("oops"),
+                    },
+                    Variant {
+                        name: "error",
+                        solve: |_| Err("too big".to_string()),
+                    },
+                ],
+            ],
+        };
+
+        let record = run(&day, 1, day.variant(1, None));
+        assert_eq!(
+            (record.status, record.answer),
+            (Status::Ok, Some("12".to_string()))
+        );
+
+        let record = run(&day, 1, day.variant(1, Some("sum")));
+        assert_eq!(record.answer.as_deref(), Some("3"));
+        let record = run(&day, 2, day.variant(2, Some("sum")));
+        assert_eq!(record.status, Status::Panic);
+        assert_eq!(record.error.as_deref(), Some("oops"));
+        let error = run(&day, 2, day.variant(2, Some("error")));
+        assert_eq!(
+            (error.status, error.error.as_deref()),
+            (Status::Error, Some("too big"))
+        );
+
+        let counted = cfg!(feature = "count-allocs");
+        assert_eq!(record.allocs.is_some(), counted);
+        assert_eq!(record.parse.is_some(), counted);
+        let record = run_on(&day, 1, day.variant(1, None), "12");
+        assert_eq!(record.answer.as_deref(), Some("12"));
+        assert_eq!(record.parse, None);
+    }
+}
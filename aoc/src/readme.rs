@@ -0,0 +1,122 @@
+use std::{fs, path::Path, time::Duration};
+
+use crate::report::{Record, Status};
+
+pub static START: &str = "<!-- progress:start -->";
+pub static END: &str = "<!-- progress:end -->";
+
+/// The puzzle title, kept as the `description` in each day's `Cargo.toml`.
+pub fn title(root: &Path, day: u32) -> Option<String> {
+    let manifest = fs::read_to_string(root.join(format!("day{day:02}/Cargo.toml"))).ok()?;
+    manifest.lines().find_map(|line| {
+        let value = line.strip_prefix("description")?.trim_start();
+        let value = value.strip_prefix('=')?.trim();
+        Some(value.trim_matches('"').to_string())
+    })
+}
+
+fn status(record: Option<&Record>) -> &'static str {
+    match record.map(|r| r.status) {
+        Some(Status::Ok) => "⭐",
+        Some(Status::Unimplemented) | None => "pending",
+        Some(status) => status.as_str(),
+    }
+}
+
+pub fn table(records: &[Record], title: impl Fn(u32) -> Option<String>) -> String {
+    let mut days: Vec<u32> = records.iter().map(|r| r.day).collect();
+    days.dedup();
+
+    let mut out = String::from(
+        "| Day | Title | Part I | Part II | Runtime |\n\
+         |----:|-------|:------:|:-------:|--------:|\n",
+    );
+    for day in days {
+        let part = |n| records.iter().find(|r| r.day == day && r.part == n);
+        let elapsed: Duration = [part(1), part(2)].iter().flatten().map(|r| r.elapsed).sum();
+        out += &format!(
+            "| [{day:02}](day{day:02}/src/lib.rs) | {} | {} | {} | {:.1}ms |\n",
+            title(day).unwrap_or_default(),
+            status(part(1)),
+            status(part(2)),
+            elapsed.as_secs_f64() * 1000.0
+        );
+    }
+    out
+}
+
+/// Replaces whatever is between the progress markers with `table`, adding
+/// the markers at the end if they aren't there yet.
+pub fn update(readme: &str, table: &str) -> String {
+    let section = format!("{START}\n{table}{END}");
+    match (readme.find(START), readme.find(END)) {
+        (Some(start), Some(end)) if start < end => {
+            format!(
+                "{}{section}{}",
+                &readme[..start],
+                &readme[end + END.len()..]
+            )
+        }
+        _ => format!("{}\n\n{section}\n", readme.trim_end()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::{table, update};
+    use crate::report::{Record, Status};
+
+    fn record(day: u32, part: usize, status: Status) -> Record {
+        Record {
+            day,
+            part,
+            status,
+            answer: None,
+            elapsed: Duration::from_micros(12_340),
+            error: None,
+            allocs: None,
+            parse: None,
+        }
+    }
+
+    #[test]
+    fn test_table() {
+        let records = [
+            record(1, 1, Status::Ok),
+            record(1, 2, Status::Ok),
+            record(5, 1, Status::Ok),
+            record(5, 2, Status::Unimplemented),
+        ];
+        let t = table(&records, |day| {
+            (day == 1).then(|| "Trebuchet?!".to_string())
+        });
+        let lines: Vec<_> = t.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(
+            lines[2],
+            "| [01](day01/src/lib.rs) | Trebuchet?! | ⭐ | ⭐ | 24.7ms |"
+        );
+        assert_eq!(
+            lines[3],
+            "| [05](day05/src/lib.rs) |  | ⭐ | pending | 24.7ms |"
+        );
+    }
+
+    #[test]
+    fn test_update() {
+        let readme = "# Advent of Code 2023";
+        let once = update(readme, "| a |\n");
+        assert_eq!(
+            once,
+            "# Advent of Code 2023\n\n<!-- progress:start -->\n| a |\n<!-- progress:end -->\n"
+        );
+
+        let twice = update(&(once.clone() + "\nMore text.\n"), "| b |\n");
+        assert_eq!(
+            twice,
+            "# Advent of Code 2023\n\n<!-- progress:start -->\n| b |\n<!-- progress:end -->\n\nMore text.\n"
+        );
+    }
+}
@@ -0,0 +1,558 @@
+use std::{str::FromStr, time::Duration};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Status {
+    Ok,
+    Panic,
+    Timeout,
+    OutOfMemory,
+    /// Any other way of failing, like a child process that crashed or an
+    /// input the part has no answer for.
+    Error,
+    Unimplemented,
+}
+
+impl Status {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Status::Ok => "ok",
+            Status::Panic => "panic",
+            Status::Timeout => "timeout",
+            Status::OutOfMemory => "out-of-memory",
+            Status::Error => "error",
+            Status::Unimplemented => "unimplemented",
+        }
+    }
+
+    fn parse(s: &str) -> Option<Self> {
+        match s {
+            "ok" => Some(Status::Ok),
+            "panic" => Some(Status::Panic),
+            "timeout" => Some(Status::Timeout),
+            "out-of-memory" => Some(Status::OutOfMemory),
+            "error" => Some(Status::Error),
+            "unimplemented" => Some(Status::Unimplemented),
+            _ => None,
+        }
+    }
+
+    /// Whether the part failed, rather than answering or being pending.
+    pub fn failed(self) -> bool {
+        !matches!(self, Status::Ok | Status::Unimplemented)
+    }
+}
+
+/// Heap use while something ran, as counted by the runner's allocator
+/// when it's built with the `count-allocs` feature.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Allocs {
+    /// Allocations made, counting each reallocation as one more.
+    pub count: u64,
+    /// Bytes asked for over all of them.
+    pub bytes: u64,
+    /// Most bytes on the heap at once, over what was there to begin with.
+    pub peak: u64,
+}
+
+/// Parsing the input on its own, as every part of the day does first.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Parse {
+    pub elapsed: Duration,
+    pub allocs: Allocs,
+}
+
+/// One row of output: a single part of a single day.
+#[derive(Debug, PartialEq)]
+pub struct Record {
+    pub day: u32,
+    pub part: usize,
+    pub status: Status,
+    pub answer: Option<String>,
+    /// Wall-clock time of this part, including parsing the input.
+    pub elapsed: Duration,
+    pub error: Option<String>,
+    /// What this part allocated, parsing included, if that was counted.
+    pub allocs: Option<Allocs>,
+    /// The day's parsing on its own, if allocations were counted and the
+    /// day has a parser to measure.
+    pub parse: Option<Parse>,
+}
+
+/// The record for one part, given its answer (`None` if it isn't solved
+/// yet) or the message it panicked with.
+pub fn record(
+    day: u32,
+    part: usize,
+    result: Result<Option<String>, String>,
+    elapsed: Duration,
+) -> Record {
+    let (status, answer, error) = match result {
+        Ok(Some(answer)) => (Status::Ok, Some(answer), None),
+        Ok(None) => (Status::Unimplemented, None, None),
+        Err(error) => (Status::Panic, None, Some(error)),
+    };
+
+    Record {
+        day,
+        part,
+        status,
+        answer,
+        elapsed,
+        error,
+        allocs: None,
+        parse: None,
+    }
+}
+
+/// The record for a part that returned an error rather than an answer.
+pub fn error(day: u32, part: usize, error: String, elapsed: Duration) -> Record {
+    Record {
+        status: Status::Error,
+        error: Some(error),
+        ..record(day, part, Ok(None), elapsed)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => Err(format!("unknown format {s}, expected json, csv or text")),
+        }
+    }
+}
+
+pub fn render(records: &[Record], format: Format) -> String {
+    match format {
+        Format::Text => text(records),
+        Format::Json => json(records),
+        Format::Csv => csv(records),
+    }
+}
+
+fn elapsed_ms(elapsed: Duration) -> String {
+    format!("{:.3}", elapsed.as_secs_f64() * 1000.0)
+}
+
+fn numeral(part: usize) -> &'static str {
+    if part == 1 {
+        "I:  "
+    } else {
+        "II: "
+    }
+}
+
+fn size(bytes: u64) -> String {
+    match bytes {
+        0..1024 => format!("{bytes}B"),
+        1024..1_048_576 => format!("{:.1}KB", bytes as f64 / 1024.0),
+        _ => format!("{:.1}MB", bytes as f64 / 1_048_576.0),
+    }
+}
+
+fn usage(elapsed: Duration, allocs: Allocs) -> String {
+    format!(
+        "{}ms, {} allocations, {}, peak {}",
+        elapsed_ms(elapsed),
+        allocs.count,
+        size(allocs.bytes),
+        size(allocs.peak)
+    )
+}
+
+/// The time and allocations of parsing and then of each part, for the
+/// records of one day that had their allocations counted.
+fn usages(day: &[Record]) -> String {
+    let mut out = String::new();
+    if let Some((r, parse)) = day.iter().find_map(|r| Some((r, r.parse?))) {
+        out += &format!(
+            "Day {:02}, parse:   {}\n",
+            r.day,
+            usage(parse.elapsed, parse.allocs)
+        );
+    }
+    for r in day {
+        if let Some(allocs) = r.allocs {
+            out += &format!(
+                "Day {:02}, part {}{}\n",
+                r.day,
+                numeral(r.part),
+                usage(r.elapsed, allocs)
+            );
+        }
+    }
+    out
+}
+
+/// Each part's answer, any errors, and then the time for the whole day,
+/// followed by what each part allocated if that was counted.
+fn text(records: &[Record]) -> String {
+    let mut out = String::new();
+    let mut errors = vec![];
+    let mut elapsed = Duration::ZERO;
+    let mut start = 0;
+
+    for (i, r) in records.iter().enumerate() {
+        let result = match r.status {
+            Status::Ok => r.answer.clone().unwrap_or_default(),
+            Status::Unimplemented => "pending".to_string(),
+            Status::OutOfMemory => "out of memory".to_string(),
+            status => status.as_str().to_string(),
+        };
+        out += &format!("Day {:02}, part {}{result}\n", r.day, numeral(r.part));
+        if let Some(error) = r.error.as_ref().filter(|e| !errors.contains(e)) {
+            errors.push(error);
+        }
+        elapsed += r.elapsed;
+
+        if records.get(i + 1).is_none_or(|next| next.day != r.day) {
+            for error in errors.drain(..) {
+                out += &format!("Day {:02}: {error}\n", r.day);
+            }
+            out += &format!("Day {:02}: {}ms\n", r.day, elapsed_ms(elapsed));
+            out += &usages(&records[start..=i]);
+            elapsed = Duration::ZERO;
+            start = i + 1;
+        }
+    }
+    out
+}
+
+fn json_string(s: &str) -> String {
+    let mut out = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => out += "\\\"",
+            '\\' => out += "\\\\",
+            '\n' => out += "\\n",
+            '\r' => out += "\\r",
+            '\t' => out += "\\t",
+            c if (c as u32) < 0x20 => out += &format!("\\u{:04x}", c as u32),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn json_option(s: &Option<String>) -> String {
+    s.as_deref().map_or("null".to_string(), json_string)
+}
+
+fn json_allocs(allocs: Option<Allocs>) -> String {
+    let (count, bytes, peak) = match allocs {
+        Some(a) => (a.count.to_string(), a.bytes.to_string(), a.peak.to_string()),
+        None => ("null".into(), "null".into(), "null".into()),
+    };
+    format!("\"allocations\": {count}, \"bytes\": {bytes}, \"peak_bytes\": {peak}")
+}
+
+/// An array with one object per record, always with the same keys:
+/// `day`, `part`, `status`, `answer`, `elapsed_ms`, `error`,
+/// `allocations`, `bytes`, `peak_bytes` and `parse`, the last four `null`
+/// unless allocations were counted. `parse` has `elapsed_ms` and the same
+/// allocation keys.
+fn json(records: &[Record]) -> String {
+    let rows: Vec<String> = records
+        .iter()
+        .map(|r| {
+            let parse = r.parse.map_or("null".to_string(), |p| {
+                format!(
+                    "{{\"elapsed_ms\": {}, {}}}",
+                    elapsed_ms(p.elapsed),
+                    json_allocs(Some(p.allocs))
+                )
+            });
+            format!(
+                "  {{\"day\": {}, \"part\": {}, \"status\": \"{}\", \"answer\": {}, \"elapsed_ms\": {}, \"error\": {}, {}, \"parse\": {parse}}}",
+                r.day,
+                r.part,
+                r.status.as_str(),
+                json_option(&r.answer),
+                elapsed_ms(r.elapsed),
+                json_option(&r.error),
+                json_allocs(r.allocs),
+            )
+        })
+        .collect();
+    if rows.is_empty() {
+        return "[]\n".to_string();
+    }
+    format!("[\n{}\n]\n", rows.join(",\n"))
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+/// `allocations,bytes,peak_bytes`, left empty if they weren't counted.
+fn csv_allocs(allocs: Option<Allocs>) -> String {
+    allocs.map_or(",,".to_string(), |a| {
+        format!("{},{},{}", a.count, a.bytes, a.peak)
+    })
+}
+
+fn csv(records: &[Record]) -> String {
+    let mut out = String::from(
+        "day,part,status,answer,elapsed_ms,error,allocations,bytes,peak_bytes,\
+         parse_ms,parse_allocations,parse_bytes,parse_peak_bytes\n",
+    );
+    for r in records {
+        out += &format!(
+            "{},{},{},{},{},{},{},{},{}\n",
+            r.day,
+            r.part,
+            r.status.as_str(),
+            csv_field(r.answer.as_deref().unwrap_or("")),
+            elapsed_ms(r.elapsed),
+            csv_field(r.error.as_deref().unwrap_or("")),
+            csv_allocs(r.allocs),
+            r.parse.map_or(String::new(), |p| elapsed_ms(p.elapsed)),
+            csv_allocs(r.parse.map(|p| p.allocs)),
+        );
+    }
+    out
+}
+
+/// Splits one line of CSV as written by `csv`, undoing its quoting.
+fn csv_fields(line: &str) -> Vec<String> {
+    let mut fields = vec![String::new()];
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match (c, quoted) {
+            ('"', true) if chars.peek() == Some(&'"') => {
+                chars.next();
+                fields.last_mut().unwrap().push('"');
+            }
+            ('"', _) => quoted = !quoted,
+            (',', false) => fields.push(String::new()),
+            (c, _) => fields.last_mut().unwrap().push(c),
+        }
+    }
+
+    fields
+}
+
+/// `None` for anything that isn't a duration, negative, NaN and infinite
+/// times included.
+fn from_ms(s: &str) -> Option<Duration> {
+    Duration::try_from_secs_f64(s.parse::<f64>().ok()? / 1000.0).ok()
+}
+
+fn parse_allocs(count: &str, bytes: &str, peak: &str) -> Option<Allocs> {
+    Some(Allocs {
+        count: count.parse().ok()?,
+        bytes: bytes.parse().ok()?,
+        peak: peak.parse().ok()?,
+    })
+}
+
+/// Reads back the output of `render(records, Format::Csv)`, skipping any
+/// line that doesn't make sense. Errors are assumed to fit on one line.
+/// Lines written before allocations were counted, without those columns,
+/// are read too.
+pub fn parse_csv(s: &str) -> Vec<Record> {
+    let non_empty = |s: &String| (!s.is_empty()).then(|| s.clone());
+
+    s.lines()
+        .skip(1)
+        .filter_map(|line| {
+            let fields = csv_fields(line);
+            let [day, part, status, answer, elapsed, error, rest @ ..] = &fields[..] else {
+                return None;
+            };
+            let (allocs, parse) = match rest {
+                [] => (None, None),
+                [count, bytes, peak, parse_ms, parse_count, parse_bytes, parse_peak] => {
+                    let parse = from_ms(parse_ms)
+                        .zip(parse_allocs(parse_count, parse_bytes, parse_peak))
+                        .map(|(elapsed, allocs)| Parse { elapsed, allocs });
+                    (parse_allocs(count, bytes, peak), parse)
+                }
+                _ => return None,
+            };
+            Some(Record {
+                day: day.parse().ok()?,
+                part: part.parse().ok()?,
+                status: Status::parse(status)?,
+                answer: non_empty(answer),
+                elapsed: from_ms(elapsed)?,
+                error: non_empty(error),
+                allocs,
+                parse,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::{parse_csv, record, render, Allocs, Format, Parse, Record, Status};
+
+    fn records() -> Vec<Record> {
+        let answer = |a: &str| Ok(Some(a.to_string()));
+        let panicked = || Err("thread 'main' panicked, \"oops\"".to_string());
+        vec![
+            record(3, 1, answer("4361"), Duration::from_micros(1500)),
+            record(3, 2, answer("467835"), Duration::from_micros(500)),
+            record(4, 1, answer("13"), Duration::from_millis(2)),
+            record(4, 2, Ok(None), Duration::from_millis(1)),
+            record(5, 1, panicked(), Duration::from_millis(3)),
+            record(5, 2, panicked(), Duration::from_millis(3)),
+        ]
+    }
+
+    #[test]
+    fn test_records() {
+        let statuses: Vec<_> = records().iter().map(|r| r.status).collect();
+        assert_eq!(
+            statuses,
+            [
+                Status::Ok,
+                Status::Ok,
+                Status::Ok,
+                Status::Unimplemented,
+                Status::Panic,
+                Status::Panic
+            ]
+        );
+        assert_eq!(records()[4].answer, None);
+    }
+
+    #[test]
+    fn test_json() {
+        let json = render(&records(), Format::Json);
+        let lines: Vec<_> = json.lines().collect();
+        assert_eq!(lines.len(), 8);
+        assert_eq!(
+            lines[1],
+            "  {\"day\": 3, \"part\": 1, \"status\": \"ok\", \"answer\": \"4361\", \"elapsed_ms\": 1.500, \"error\": null, \
+             \"allocations\": null, \"bytes\": null, \"peak_bytes\": null, \"parse\": null},"
+        );
+        assert_eq!(
+            lines[4],
+            "  {\"day\": 4, \"part\": 2, \"status\": \"unimplemented\", \"answer\": null, \"elapsed_ms\": 1.000, \"error\": null, \
+             \"allocations\": null, \"bytes\": null, \"peak_bytes\": null, \"parse\": null},"
+        );
+        assert!(lines[6].contains("\"error\": \"thread 'main' panicked, \\\"oops\\\"\","));
+        assert_eq!(render(&[], Format::Json), "[]\n");
+    }
+
+    #[test]
+    fn test_csv() {
+        let csv = render(&records(), Format::Csv);
+        let lines: Vec<_> = csv.lines().collect();
+        assert_eq!(
+            lines[0],
+            "day,part,status,answer,elapsed_ms,error,allocations,bytes,peak_bytes,\
+             parse_ms,parse_allocations,parse_bytes,parse_peak_bytes"
+        );
+        assert_eq!(lines[1], "3,1,ok,4361,1.500,,,,,,,,");
+        assert_eq!(lines[4], "4,2,unimplemented,,1.000,,,,,,,,");
+        assert_eq!(
+            lines[5],
+            "5,1,panic,,3.000,\"thread 'main' panicked, \"\"oops\"\"\",,,,,,,"
+        );
+    }
+
+    #[test]
+    fn test_parse_csv() {
+        let records = records();
+        let parsed = parse_csv(&render(&records, Format::Csv));
+        assert_eq!(parsed.len(), records.len());
+        for (a, b) in parsed.iter().zip(&records) {
+            assert_eq!((a.day, a.part, a.status), (b.day, b.part, b.status));
+            assert_eq!((&a.answer, &a.error), (&b.answer, &b.error));
+            assert_eq!(a.elapsed.as_micros(), b.elapsed.as_micros());
+        }
+
+        // As cached before allocations were counted.
+        let old = "day,part,status,answer,elapsed_ms,error\n3,1,ok,4361,1.500,\n";
+        assert_eq!(
+            parse_csv(old),
+            parse_csv(&render(&records[..1], Format::Csv))
+        );
+
+        // Times that aren't durations are skipped with the rest of their
+        // line.
+        for elapsed in ["-1", "NaN", "inf", "1e400"] {
+            let bad = format!("day,part,status,answer,elapsed_ms,error\n3,1,ok,4361,{elapsed},\n");
+            assert_eq!(parse_csv(&bad), [], "{elapsed}");
+        }
+    }
+
+    fn counted() -> Vec<Record> {
+        let allocs = |count, bytes, peak| Allocs { count, bytes, peak };
+        let mut records = records();
+        records.truncate(2);
+        for (r, n) in records.iter_mut().zip([10, 2000]) {
+            r.allocs = Some(allocs(n, n * 1000, n * 500));
+            r.parse = Some(Parse {
+                elapsed: Duration::from_micros(250),
+                allocs: allocs(3, 600, 600),
+            });
+        }
+        records
+    }
+
+    #[test]
+    fn test_allocs() {
+        assert_eq!(
+            render(&counted(), Format::Text),
+            "Day 03, part I:  4361\nDay 03, part II: 467835\nDay 03: 2.000ms\n\
+             Day 03, parse:   0.250ms, 3 allocations, 600B, peak 600B\n\
+             Day 03, part I:  1.500ms, 10 allocations, 9.8KB, peak 4.9KB\n\
+             Day 03, part II: 0.500ms, 2000 allocations, 1.9MB, peak 976.6KB\n"
+        );
+
+        let csv = render(&counted(), Format::Csv);
+        assert_eq!(
+            csv.lines().nth(1),
+            Some("3,1,ok,4361,1.500,,10,10000,5000,0.250,3,600,600")
+        );
+        assert_eq!(parse_csv(&csv), counted());
+
+        let json = render(&counted(), Format::Json);
+        assert!(json.lines().nth(1).unwrap().ends_with(
+            "\"allocations\": 10, \"bytes\": 10000, \"peak_bytes\": 5000, \
+             \"parse\": {\"elapsed_ms\": 0.250, \"allocations\": 3, \"bytes\": 600, \"peak_bytes\": 600}},"
+        ));
+    }
+
+    #[test]
+    fn test_text() {
+        let text = render(&records(), Format::Text);
+        assert_eq!(
+            text,
+            "Day 03, part I:  4361\nDay 03, part II: 467835\nDay 03: 2.000ms\n\
+             Day 04, part I:  13\nDay 04, part II: pending\nDay 04: 3.000ms\n\
+             Day 05, part I:  panic\nDay 05, part II: panic\n\
+             Day 05: thread 'main' panicked, \"oops\"\nDay 05: 6.000ms\n"
+        );
+    }
+
+    #[test]
+    fn test_format() {
+        assert_eq!("json".parse(), Ok(Format::Json));
+        assert!("yaml".parse::<Format>().is_err());
+    }
+}
@@ -0,0 +1,195 @@
+use std::ops::Range;
+
+use crate::{
+    days::Day,
+    isolate::{self, Limits},
+    run_on,
+    verify::outcome,
+};
+
+/// `message` with every run of digits replaced by `N`, so a panic still
+/// counts as the same one when indices and lengths shrink with the input.
+fn normalise(message: &str) -> String {
+    let mut out = String::new();
+    for c in message.chars() {
+        if !c.is_ascii_digit() {
+            out.push(c);
+        } else if !out.ends_with('N') {
+            out.push('N');
+        }
+    }
+    out
+}
+
+/// How `day` fails on `input`, if it does: the first part (of `parts`) with
+/// a variant that panics, errs, times out or is killed, or whose variants
+/// disagree. Each variant runs in a child process within `limits`, or in
+/// this one if there are none, so that a candidate that hangs or aborts
+/// can't end the search.
+pub fn failure(day: &Day, parts: &[usize], input: &str, limits: Option<&Limits>) -> Option<String> {
+    for &part in parts {
+        let mut outcomes = vec![];
+        for variant in day.parts[part - 1] {
+            let record = match limits {
+                Some(limits) => isolate::run(day, part, variant, input, limits),
+                None => run_on(day, part, variant, input),
+            };
+            if record.status.failed() {
+                let error = record.error.as_deref().unwrap_or("");
+                return Some(format!(
+                    "part {part}, {}: {}: {}",
+                    variant.name,
+                    record.status.as_str(),
+                    normalise(error)
+                ));
+            }
+            outcomes.push(outcome(&record));
+        }
+        if outcomes.iter().any(|o| *o != outcomes[0]) {
+            return Some(format!("part {part}: variants disagree"));
+        }
+    }
+    None
+}
+
+/// Zeller's ddmin: the smallest run of `items` found by repeatedly keeping
+/// only one chunk, or dropping one, while `fails` still holds, with the
+/// chunks halving whenever neither works.
+fn ddmin<'a>(mut items: Vec<&'a str>, fails: &mut impl FnMut(&[&'a str]) -> bool) -> Vec<&'a str> {
+    let mut n = 2;
+
+    while items.len() >= 2 {
+        let size = items.len().div_ceil(n);
+        let chunks: Vec<Range<usize>> = (0..items.len())
+            .step_by(size)
+            .map(|start| start..(start + size).min(items.len()))
+            .collect();
+
+        if let Some(chunk) = chunks.iter().find(|c| fails(&items[(*c).clone()])) {
+            items = items[chunk.clone()].to_vec();
+            n = 2;
+            continue;
+        }
+
+        let complement = |c: &Range<usize>| [&items[..c.start], &items[c.end..]].concat();
+        if let Some(rest) = chunks.iter().map(complement).find(|rest| fails(rest)) {
+            items = rest;
+            n = (n - 1).max(2);
+            continue;
+        }
+
+        if n >= items.len() {
+            break;
+        }
+        n = (n * 2).min(items.len());
+    }
+
+    items
+}
+
+/// The smallest input found that `fails` still holds for: first whole
+/// sections are dropped, split at blank lines like the maps of day 5, then
+/// single lines within each section, like the games, rows or cards of other
+/// days. The blank lines between sections are always kept.
+pub fn shrink(input: &str, mut fails: impl FnMut(&str) -> bool) -> String {
+    let newline = if input.ends_with('\n') { "\n" } else { "" };
+    let body = input.trim_end_matches('\n');
+    let join = |sections: &[Vec<&str>]| {
+        let sections: Vec<String> = sections.iter().map(|lines| lines.join("\n")).collect();
+        sections.join("\n\n") + newline
+    };
+
+    let sections = ddmin(body.split("\n\n").collect(), &mut |sections| {
+        fails(&(sections.join("\n\n") + newline))
+    });
+    let mut sections: Vec<Vec<&str>> = sections.iter().map(|s| s.split('\n').collect()).collect();
+
+    for i in 0..sections.len() {
+        let lines = ddmin(sections[i].clone(), &mut |lines| {
+            let mut candidate = sections.clone();
+            candidate[i] = lines.to_vec();
+            fails(&join(&candidate))
+        });
+        sections[i] = lines;
+    }
+    join(&sections)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{failure, normalise, shrink};
+    use crate::days::{Day, Variant};
+
+    #[test]
+    fn test_normalise() {
+        assert_eq!(
+            normalise("index out of bounds: the len is 200 but the index is 201"),
+            "index out of bounds: the len is N but the index is N"
+        );
+    }
+
+    #[test]
+    fn test_shrink_lines() {
+        let input: String = (0..100).map(|i| format!("{i}\n")).collect();
+        let shrunk = shrink(&input, |s| {
+            s.lines().any(|l| l == "17") && s.lines().any(|l| l == "64")
+        });
+        assert_eq!(shrunk, "17\n64\n");
+
+        assert_eq!(shrink("a\nb\nc", |s| s.contains('b')), "b");
+    }
+
+    #[test]
+    fn test_shrink_sections() {
+        let input = "seeds: 1\n\na map:\n1 2 3\n4 5 6\n\nb map:\n4 4 4\n7 8 9\n\nc map:\n1 1 1\n";
+        let shrunk = shrink(input, |s| {
+            s.starts_with("seeds") && s.contains("b map:") && s.contains("7 8 9")
+        });
+        assert_eq!(shrunk, "seeds: 1\n\nb map:\n7 8 9\n");
+        assert!(day05::parse_maps(&shrunk).is_ok());
+    }
+
+    #[test]
+    fn test_failure() {
+        let day = Day {
+            day: 7,
+            input: "",
+            parse: None,
+            parts: [
+                &[
+                    Variant {
+                        name: "default",
+                        solve: |i| Ok(Some(i.lines().count().to_string())),
+                    },
+                    Variant {
+                        name: "other",
+                        solve: |i| Ok(Some(i.lines().filter(|l| *l != "x").count().to_string())),
+                    },
+                ],
+                &[Variant {
+                    name: "default",
+                    solve: |i| {
+                        let n: usize = i.lines().count();
+                        assert!(n < 5, "too many lines: {n}");
+                        Ok(Some(n.to_string()))
+                    },
+                }],
+            ],
+        };
+
+        assert_eq!(failure(&day, &[1, 2], "a\nb\n", None), None);
+        assert_eq!(
+            failure(&day, &[1, 2], "a\nx\n", None).as_deref(),
+            Some("part 1: variants disagree")
+        );
+        assert_eq!(
+            failure(&day, &[2], "1\n2\n3\n4\n5\n6\n", None).as_deref(),
+            Some("part 2, default: panic: too many lines: N")
+        );
+
+        let input: String = (0..50).map(|i| format!("{i}\n")).collect();
+        let wanted = failure(&day, &[2], &input, None);
+        let shrunk = shrink(&input, |s| failure(&day, &[2], s, None) == wanted);
+        assert_eq!(shrunk.lines().count(), 5);
+    }
+}
@@ -0,0 +1,115 @@
+use crate::{
+    days::Day,
+    report::{Record, Status},
+    run,
+};
+
+/// What a run came to, for comparing variants: the answer, `pending` or
+/// the error.
+pub fn outcome(record: &Record) -> String {
+    match record.status {
+        Status::Ok => record.answer.clone().unwrap_or_default(),
+        Status::Unimplemented => "pending".to_string(),
+        status => format!(
+            "{}: {}",
+            status.as_str(),
+            record.error.as_deref().unwrap_or("")
+        ),
+    }
+}
+
+/// Runs every variant of every part of `days`, one at a time so their
+/// timings compare, and lists them side by side. Also returns whether each
+/// part's variants all gave the same answer without failing.
+pub fn verify(days: &[&Day]) -> (String, bool) {
+    let mut out = String::new();
+    let mut agreed = true;
+
+    for day in days {
+        for part in 1..=2 {
+            let runs: Vec<(&str, String, Record)> = day.parts[part - 1]
+                .iter()
+                .map(|variant| {
+                    let record = run(day, part, variant);
+                    (variant.name, outcome(&record), record)
+                })
+                .collect();
+
+            let ok = runs
+                .iter()
+                .all(|(_, outcome, record)| !record.status.failed() && *outcome == runs[0].1);
+            agreed &= ok;
+
+            let numeral = if part == 1 { "I" } else { "II" };
+            let verdict = if ok { "ok" } else { "MISMATCH" };
+            out += &format!("Day {:02}, part {numeral}: {verdict}\n", day.day);
+
+            let name_width = runs.iter().map(|(name, ..)| name.len()).max().unwrap_or(0);
+            let outcome_width = runs.iter().map(|(_, o, _)| o.len()).max().unwrap_or(0);
+            for (name, outcome, record) in &runs {
+                out += &format!(
+                    "  {name:name_width$}  {outcome:outcome_width$}  {:.3}ms\n",
+                    record.elapsed.as_secs_f64() * 1000.0
+                );
+            }
+        }
+    }
+
+    (out, agreed)
+}
+
+#[cfg(test)]
+mod test {
+    use super::verify;
+    use crate::days::{Day, Variant};
+
+    #[test]
+    fn test_verify() {
+        let day = Day {
+            day: 7,
+            input: "3",
+            parse: None,
+            parts: [
+                &[
+                    Variant {
+                        name: "default",
+                        solve: |i| Ok(Some(i.to_string())),
+                    },
+                    Variant {
+                        name: "other",
+                        solve: |_| Ok(Some("3".to_string())),
+                    },
+                ],
+                &[Variant {
+                    name: "default",
+                    solve: |_| Ok(None),
+                }],
+            ],
+        };
+        let (out, agreed) = verify(&[&day]);
+        assert!(agreed);
+        assert!(out.starts_with("Day 07, part I: ok\n  default  3  "));
+        assert!(out.contains("\n  other    3  "));
+        assert!(out.contains("Day 07, part II: ok\n  default  pending  "));
+
+        let day = Day {
+            parts: [
+                day.parts[0],
+                &[
+                    Variant {
+                        name: "default",
+                        solve: |_| Ok(Some("1".to_string())),
+                    },
+                    Variant {
+                        name: "broken",
+                        solve: |_| Ok(Some("2".to_string())),
+                    },
+                ],
+            ],
+            ..day
+        };
+        let (out, agreed) = verify(&[&day]);
+        assert!(!agreed);
+        assert!(out.contains("Day 07, part II: MISMATCH\n  default  1  "));
+    }
+}
@@ -0,0 +1,240 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use crate::{
+    report::{self, Record},
+    verify::outcome,
+};
+
+/// How often to look for changes.
+const POLL: Duration = Duration::from_millis(200);
+/// How long files must stay the same after a change before re-running, so a
+/// burst of saves only runs once.
+const QUIET: Duration = Duration::from_millis(300);
+
+type Snapshot = BTreeMap<PathBuf, SystemTime>;
+
+/// Every file under `paths`, with when it was last modified.
+fn snapshot(paths: &[PathBuf]) -> Snapshot {
+    let mut files = BTreeMap::new();
+    let mut pending = paths.to_vec();
+
+    while let Some(path) = pending.pop() {
+        if let Ok(entries) = fs::read_dir(&path) {
+            pending.extend(entries.flatten().map(|e| e.path()));
+        } else if let Ok(modified) = fs::metadata(&path).and_then(|m| m.modified()) {
+            files.insert(path, modified);
+        }
+    }
+
+    files
+}
+
+/// Waits for something under `paths` to change from `last`, then for
+/// things to settle, and returns how they settled.
+fn wait_for_change(paths: &[PathBuf], mut last: Snapshot) -> Snapshot {
+    loop {
+        thread::sleep(POLL);
+        let now = snapshot(paths);
+        if now != last {
+            last = now;
+            break;
+        }
+    }
+
+    loop {
+        thread::sleep(QUIET);
+        let now = snapshot(paths);
+        if now == last {
+            return now;
+        }
+        last = now;
+    }
+}
+
+/// Passed and failed tests summed over the `test result:` lines of
+/// `cargo test`, or `None` if there aren't any, as when the build failed.
+fn test_counts(output: &str) -> Option<(usize, usize)> {
+    let count = |line: &str, label: &str| {
+        line.split(';')
+            .find_map(|field| {
+                let count = field.trim().strip_suffix(label)?;
+                count.split_whitespace().last()?.parse::<usize>().ok()
+            })
+            .unwrap_or(0)
+    };
+
+    output
+        .lines()
+        .filter_map(|line| line.split_once("test result: "))
+        .map(|(_, result)| (count(result, "passed"), count(result, "failed")))
+        .reduce(|(p, f), (q, g)| (p + q, f + g))
+}
+
+/// The first error `cargo` reported, for when there's nothing else to go on.
+fn first_error(stderr: &str) -> String {
+    stderr
+        .lines()
+        .find(|line| line.starts_with("error"))
+        .unwrap_or("failed")
+        .to_string()
+}
+
+/// One line per part with what it came to, and what it was on the previous
+/// run if that's different.
+fn diff(previous: &[Record], current: &[Record]) -> String {
+    let mut out = String::new();
+
+    for r in current {
+        let numeral = if r.part == 1 { "I:  " } else { "II: " };
+        let now = outcome(r);
+        out += &format!("Day {:02}, part {numeral}{now}", r.day);
+
+        let before = previous.iter().find(|p| (p.day, p.part) == (r.day, r.part));
+        if let Some(before) = before.map(outcome).filter(|before| *before != now) {
+            out += &format!(" (was {before})");
+        }
+        out.push('\n');
+    }
+
+    out
+}
+
+fn cargo(root: &Path, args: &[&str]) -> Result<(String, String, bool), String> {
+    let output = Command::new("cargo")
+        .args(args)
+        .current_dir(root)
+        .output()
+        .map_err(|e| format!("Can't run cargo: {e}"))?;
+    Ok((
+        String::from_utf8_lossy(&output.stdout).into_owned(),
+        String::from_utf8_lossy(&output.stderr).into_owned(),
+        output.status.success(),
+    ))
+}
+
+/// Builds and tests `day`, then runs it with a freshly built runner, as
+/// this one has the old code linked in.
+fn check(root: &Path, day: &str, previous: &[Record]) -> Result<(String, Vec<Record>), String> {
+    let (stdout, stderr, ok) = cargo(root, &["test", "-q", "-p", day])?;
+    let tests = match test_counts(&stdout) {
+        Some((passed, 0)) if ok => format!("Tests: ok, {passed} passed\n"),
+        Some((passed, failed)) => format!("Tests: FAILED, {passed} passed, {failed} failed\n"),
+        None => format!("Build: {}\n", first_error(&stderr)),
+    };
+
+    let run = [
+        "run",
+        "-q",
+        "--release",
+        "--bin",
+        "aoc",
+        "--",
+        day,
+        "--format",
+        "csv",
+    ];
+    let (stdout, stderr, ok) = cargo(root, &run)?;
+    if !ok {
+        return Ok((tests + &format!("Run: {}\n", first_error(&stderr)), vec![]));
+    }
+    let records = report::parse_csv(&stdout);
+
+    Ok((tests + &diff(previous, &records), records))
+}
+
+/// `aoc watch <day>`: checks the day, then again whenever its code,
+/// fixtures or input change, until interrupted.
+pub fn watch(root: &Path, day: &str) -> ! {
+    let dir = root.join(day);
+    let paths = [dir.join("src"), dir.join("tests"), dir.join("input.txt")];
+    let mut last = snapshot(&paths);
+    let mut records = vec![];
+
+    loop {
+        println!("Checking {day}...");
+        match check(root, day, &records) {
+            Ok((summary, latest)) => {
+                print!("{summary}");
+                if !latest.is_empty() {
+                    records = latest;
+                }
+            }
+            Err(e) => println!("{e}"),
+        }
+        println!("Waiting for changes to {}", dir.display());
+        last = wait_for_change(&paths, last);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{fs, time::Duration};
+
+    use super::{diff, first_error, snapshot, test_counts};
+    use crate::report::record;
+
+    #[test]
+    fn test_test_counts() {
+        let output = "\nrunning 5 tests\n.....\n\
+                      test result: ok. 5 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.02s\n\n\
+                      test result: FAILED. 2 passed; 1 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.00s\n";
+        assert_eq!(test_counts(output), Some((7, 1)));
+        assert_eq!(test_counts("error: could not compile `day01`"), None);
+    }
+
+    #[test]
+    fn test_first_error() {
+        let stderr = "warning: unused\nerror[E0425]: cannot find value `x`\nerror: aborting\n";
+        assert_eq!(first_error(stderr), "error[E0425]: cannot find value `x`");
+        assert_eq!(first_error(""), "failed");
+    }
+
+    #[test]
+    fn test_diff() {
+        let ms = Duration::from_millis(1);
+        let answer = |a: &str| Ok(Some(a.to_string()));
+        let previous = [
+            record(1, 1, answer("142"), ms),
+            record(1, 2, answer("280"), ms),
+        ];
+        let current = [
+            record(1, 1, answer("142"), ms),
+            record(1, 2, Err("oops".to_string()), ms),
+        ];
+
+        assert_eq!(
+            diff(&previous, &current),
+            "Day 01, part I:  142\nDay 01, part II: panic: oops (was 280)\n"
+        );
+        assert_eq!(
+            diff(&[], &previous),
+            "Day 01, part I:  142\nDay 01, part II: 280\n"
+        );
+    }
+
+    #[test]
+    fn test_snapshot() {
+        let dir = std::env::temp_dir().join(format!("aoc-watch-{}", std::process::id()));
+        fs::create_dir_all(dir.join("src")).unwrap();
+        fs::write(dir.join("src/lib.rs"), "fn a() {}").unwrap();
+        fs::write(dir.join("input.txt"), "1").unwrap();
+
+        let paths = [dir.join("src"), dir.join("input.txt"), dir.join("missing")];
+        let before = snapshot(&paths);
+        assert_eq!(before.len(), 2);
+        assert_eq!(snapshot(&paths), before);
+
+        fs::write(dir.join("src/main.rs"), "fn main() {}").unwrap();
+        assert_eq!(snapshot(&paths).len(), 3);
+
+        fs::remove_dir_all(&dir).unwrap();
+        assert!(snapshot(&paths).is_empty());
+    }
+}
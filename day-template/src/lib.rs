@@ -0,0 +1,15 @@
+pub static INPUT: &str = include_str!("../input.txt");
+
+/// `None` until solved, so the runner shows the part as pending.
+pub fn part1(_input: &str) -> Option<u32> {
+    None
+}
+
+pub fn part2(_input: &str) -> Option<u32> {
+    None
+}
+
+#[cfg(test)]
+mod test {
+    harness::fixtures!(crate::part1, crate::part2);
+}
@@ -0,0 +1,90 @@
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::NUMBERS;
+
+/// How calibration lines are made up.
+#[derive(Clone, Debug)]
+pub struct Params {
+    /// Percent chance that a number word runs straight into another that
+    /// starts with its last letter, as in `oneight`.
+    pub overlap: u32,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params { overlap: 30 }
+    }
+}
+
+/// A number word, followed while `overlap` allows by up to three words
+/// sharing a letter with the one before, e.g. `eightwone`.
+fn words(rng: &mut impl Rng, params: &Params, out: &mut String) {
+    let mut word = NUMBERS[rng.gen_range(0..NUMBERS.len())];
+    out.push_str(word);
+
+    for _ in 0..3 {
+        if rng.gen_range(0..100) >= params.overlap {
+            break;
+        }
+        let last = word.as_bytes()[word.len() - 1];
+        let next: Vec<&str> = NUMBERS
+            .iter()
+            .copied()
+            .filter(|w| w.as_bytes()[0] == last)
+            .collect();
+        if next.is_empty() {
+            break;
+        }
+        word = next[rng.gen_range(0..next.len())];
+        out.push_str(&word[1..]);
+    }
+}
+
+/// A line shaped like the puzzle input: letters, digits and number words,
+/// with at least one digit so part I has an answer for it.
+fn line(rng: &mut impl Rng, params: &Params) -> String {
+    let mut out = String::new();
+    let digit_at = rng.gen_range(0..6);
+
+    for i in 0..6 {
+        if i == digit_at {
+            out.push(char::from(b'1' + rng.gen_range(0..9)));
+        }
+        match rng.gen_range(0..3) {
+            0 => words(rng, params, &mut out),
+            1 => out.push(char::from(b'1' + rng.gen_range(0..9))),
+            _ => {
+                for _ in 0..rng.gen_range(1..=4) {
+                    out.push(char::from(b'a' + rng.gen_range(0..26)));
+                }
+            }
+        }
+    }
+
+    out
+}
+
+/// `size` calibration lines; the same seed always gives the same lines.
+pub fn generate(seed: u64, size: usize, params: &Params) -> String {
+    let mut rng = StdRng::seed_from_u64(seed);
+    (0..size).map(|_| line(&mut rng, params) + "\n").collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{generate, Params};
+
+    #[test]
+    fn test_generate() {
+        let params = Params::default();
+        let input = generate(7, 200, &params);
+        assert_eq!(input, generate(7, 200, &params));
+        assert_ne!(input, generate(8, 200, &params));
+        assert_eq!(input.lines().count(), 200);
+        assert!(input.lines().all(|l| l.bytes().any(|b| b.is_ascii_digit())));
+
+        let overlapping = generate(7, 200, &Params { overlap: 100 });
+        assert!(overlapping.contains("oneight") || overlapping.contains("eightwo"));
+        assert_eq!(crate::part2_ends(&overlapping), crate::part2(&overlapping));
+    }
+}
@@ -0,0 +1,246 @@
+pub mod generator;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+pub static INPUT: &str = include_str!("../input.txt");
+
+fn parse_line(line: &str) -> Vec<u8> {
+    line.bytes()
+        .filter(u8::is_ascii_digit)
+        .map(|b| b - b'0')
+        .collect()
+}
+
+static NUMBERS: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+fn parse_line2(line: &str) -> Vec<u8> {
+    // Bytes rather than `str` slices, which panic off a char boundary.
+    let line = line.as_bytes();
+    let mut digits: Vec<u8> = vec![];
+
+    let mut i = 0;
+
+    while i < line.len() {
+        if line[i].is_ascii_digit() {
+            digits.push(line[i] - b'0');
+        } else {
+            for (index, word) in NUMBERS.iter().enumerate() {
+                if line[i..].starts_with(word.as_bytes()) {
+                    digits.push((index + 1) as u8);
+                    // This is annoying, digits _can_ overlap:
+                    // oneight twone threeight fiveight sevenine eightwo eighthree nineight
+                    i += word.len() - 2;
+                    break;
+                }
+            }
+        }
+
+        i += 1;
+    }
+
+    digits
+}
+
+fn calibration_value(digits: Vec<u8>) -> u32 {
+    format!(
+        "{}{}",
+        digits.first().unwrap_or(&0),
+        digits.last().unwrap_or(&0)
+    )
+    .parse()
+    .unwrap_or(0)
+}
+
+fn part(input: &str, parser: impl Fn(&str) -> Vec<u8>) -> u32 {
+    input
+        .split('\n')
+        .map(parser)
+        .map(calibration_value)
+        .reduce(|acc, e| acc + e)
+        .unwrap()
+}
+
+#[cfg(feature = "parallel")]
+fn par_part(input: &str, parser: impl Fn(&str) -> Vec<u8> + Sync + Send) -> u32 {
+    input
+        .par_split('\n')
+        .map(parser)
+        .map(calibration_value)
+        .sum()
+}
+
+pub fn part1(input: &str) -> u32 {
+    part(input, parse_line)
+}
+
+pub fn part2(input: &str) -> u32 {
+    part(input, parse_line2)
+}
+
+/// The digit, as a numeral or spelled out, that `s` starts with.
+fn digit_at(s: &str) -> Option<u32> {
+    s.chars().next()?.to_digit(10).or_else(|| {
+        NUMBERS
+            .iter()
+            .position(|word| s.starts_with(word))
+            .map(|index| index as u32 + 1)
+    })
+}
+
+/// Part II without collecting every digit of a line: only the first and
+/// last count, so search in from each end and stop at the first hit.
+pub fn part2_ends(input: &str) -> u32 {
+    input
+        .split('\n')
+        .map(|line| {
+            let starts = (0..line.len()).filter(|i| line.is_char_boundary(*i));
+            let first = starts.clone().find_map(|i| digit_at(&line[i..]));
+            let last = starts.rev().find_map(|i| digit_at(&line[i..]));
+            first.zip(last).map_or(0, |(a, b)| a * 10 + b)
+        })
+        .sum()
+}
+
+#[cfg(feature = "parallel")]
+pub fn par_part1(input: &str) -> u32 {
+    par_part(input, parse_line)
+}
+
+#[cfg(feature = "parallel")]
+pub fn par_part2(input: &str) -> u32 {
+    par_part(input, parse_line2)
+}
+
+#[cfg(test)]
+mod test {
+    use proptest::prelude::*;
+    use regex::Regex;
+
+    use crate::NUMBERS;
+
+    harness::fixtures!(crate::part1, crate::part2);
+
+    #[test]
+    fn test_parse_line2() {
+        assert_eq!(crate::parse_line2("two1nine"), [2, 1, 9]);
+        assert_eq!(crate::parse_line2("eightwothree"), [8, 2, 3]);
+        assert_eq!(crate::parse_line2("abcone2threexyz"), [1, 2, 3]);
+        assert_eq!(crate::parse_line2("4nineeightseven2"), [4, 9, 8, 7, 2]);
+        assert_eq!(crate::parse_line2("zoneight234"), [1, 8, 2, 3, 4]);
+        assert_eq!(crate::parse_line2("7pqrstsixteen"), [7, 6]);
+
+        assert_eq!(crate::parse_line2("144six"), [1, 4, 4, 6]);
+        assert_eq!(
+            crate::parse_line2("kbjtmgfrx3mpmjhncfl78nine"),
+            [3, 7, 8, 9]
+        );
+        assert_eq!(crate::parse_line2("7sgnlbdfivecxz"), [7, 5]);
+        assert_eq!(crate::parse_line2("oneqrbbnrdxgbbfl3"), [1, 3]);
+        assert_eq!(crate::parse_line2("8ndmrfggfz1six87"), [8, 1, 6, 8, 7]);
+        assert_eq!(
+            crate::parse_line2("hjkfb8vhrhnlmbhbl59rxplvmgzspfour"),
+            [8, 5, 9, 4]
+        );
+        assert_eq!(crate::parse_line2("3sixjhdn4hckqsnvseven"), [3, 6, 4, 7]);
+        assert_eq!(crate::parse_line2("zmkgmlpfsixxhmv25bqlgm5"), [6, 2, 5, 5]);
+        assert_eq!(crate::parse_line2("three48eighttwo"), [3, 4, 8, 8, 2]);
+        assert_eq!(
+            crate::parse_line2("rgfzfourbmpxzrh6dfjcdkhqhcdkpfpk"),
+            [4, 6]
+        );
+        assert_eq!(
+            crate::parse_line2("bpccbcqmlstwos8threenineeightg8"),
+            [2, 8, 3, 9, 8, 8]
+        );
+        assert_eq!(crate::parse_line2("nbcpd2prckbshrbvsmrmlhxdkq"), [2]);
+        assert_eq!(crate::parse_line2("6flfsxv"), [6]);
+        assert_eq!(crate::parse_line2("84xqeightseven"), [8, 4, 8, 7]);
+    }
+
+    #[test]
+    fn test_part2_ends() {
+        for input in [
+            crate::INPUT,
+            include_str!("../tests/fixtures/edge_cases.txt"),
+            include_str!("../tests/fixtures/overlapping.txt"),
+            include_str!("../tests/fixtures/non_ascii.txt"),
+            "no digits\n7\nsix",
+        ] {
+            assert_eq!(crate::part2_ends(input), crate::part2(input));
+        }
+    }
+
+    /// First and last digit, spelled or not, as found by regexes rather than
+    /// scanning. The last is the first match of the reversed words in the
+    /// reversed line, so overlaps like `oneight` come out right.
+    fn reference(input: &str) -> u32 {
+        let words = NUMBERS.join("|");
+        let reversed: Vec<String> = NUMBERS.iter().map(|w| w.chars().rev().collect()).collect();
+        let forwards = Regex::new(&format!(r"\d|{words}")).unwrap();
+        let backwards = Regex::new(&format!(r"\d|{}", reversed.join("|"))).unwrap();
+
+        let value = |word: &str| {
+            word.parse::<u32>().unwrap_or_else(|_| {
+                let position = NUMBERS.iter().position(|w| *w == word);
+                let position = position.or_else(|| reversed.iter().position(|w| w == word));
+                position.unwrap() as u32 + 1
+            })
+        };
+
+        input
+            .split('\n')
+            .map(|line| {
+                let backwards_line: String = line.chars().rev().collect();
+                let first = forwards.find(line).map(|m| value(m.as_str()));
+                let last = backwards.find(&backwards_line).map(|m| value(m.as_str()));
+                first.zip(last).map_or(0, |(a, b)| a * 10 + b)
+            })
+            .sum()
+    }
+
+    /// Lines built from number words, digits and letters that appear in
+    /// them, so words often run into each other.
+    fn line() -> impl Strategy<Value = String> {
+        let token = prop_oneof![
+            prop::sample::select(&NUMBERS[..]).prop_map(str::to_string),
+            "[0-9]",
+            "[a-z]",
+            "[eghinorstuvwx]{1,3}",
+        ];
+        prop::collection::vec(token, 0..10).prop_map(|tokens| tokens.concat())
+    }
+
+    proptest! {
+        #[test]
+        fn test_part1_reference(lines in prop::collection::vec("[a-z0-9]{0,12}", 1..10)) {
+            let input = lines.join("\n");
+            let digits = Regex::new(r"\d").unwrap();
+            let expected: u32 = lines
+                .iter()
+                .map(|line| {
+                    let mut found = digits.find_iter(line).map(|m| m.as_str().parse::<u32>().unwrap());
+                    let first = found.next();
+                    first.map_or(0, |a| a * 10 + found.last().unwrap_or(a))
+                })
+                .sum();
+            prop_assert_eq!(crate::part1(&input), expected);
+        }
+
+        #[test]
+        fn test_part2_reference(lines in prop::collection::vec(line(), 1..10)) {
+            let input = lines.join("\n");
+            prop_assert_eq!(crate::part2(&input), reference(&input));
+            prop_assert_eq!(crate::part2_ends(&input), reference(&input));
+        }
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_par_parts() {
+        assert_eq!(crate::par_part1(crate::INPUT), crate::part1(crate::INPUT));
+        assert_eq!(crate::par_part2(crate::INPUT), crate::part2(crate::INPUT));
+    }
+}
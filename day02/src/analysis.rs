@@ -0,0 +1,138 @@
+use crate::parser::{Game, Show};
+use crate::{Bag, COLOURS};
+
+/// `ln(n choose k)`, or `None` when `k > n` and there are no ways to choose.
+fn ln_choose(n: u32, k: u32) -> Option<f64> {
+    if k > n {
+        return None;
+    }
+    let k = k.min(n - k);
+    Some((1..=k).map(|i| ((n - k + i) as f64 / i as f64).ln()).sum())
+}
+
+fn round_bag(round: &[Show]) -> Bag {
+    let mut drawn = Bag::default();
+    for show in round {
+        *drawn.get_mut(&show.colour) += show.number;
+    }
+    drawn
+}
+
+impl Bag {
+    pub fn total(&self) -> u32 {
+        self.red + self.green + self.blue
+    }
+
+    /// Log-probability of drawing exactly `drawn` in one handful taken from
+    /// this bag without replacement (the multivariate hypergeometric).
+    fn ln_draw(&self, drawn: &Bag) -> f64 {
+        let ways = COLOURS.iter().try_fold(0.0, |acc, c| {
+            ln_choose(self.get(c), drawn.get(c)).map(|w| acc + w)
+        });
+        match (ways, ln_choose(self.total(), drawn.total())) {
+            (Some(ways), Some(all)) => ways - all,
+            _ => f64::NEG_INFINITY,
+        }
+    }
+}
+
+impl Game {
+    /// Log-likelihood of each observed round, assuming the cubes are put
+    /// back in the bag between rounds.
+    pub fn round_likelihoods(&self, bag: &Bag) -> Vec<f64> {
+        self.rounds
+            .iter()
+            .map(|round| bag.ln_draw(&round_bag(round)))
+            .collect()
+    }
+
+    /// Log-likelihood of the whole game under `bag`; `-inf` if any round is
+    /// impossible.
+    pub fn likelihood(&self, bag: &Bag) -> f64 {
+        self.round_likelihoods(bag).iter().sum()
+    }
+
+    /// The bag of at most `max_total` cubes under which this game is most
+    /// likely, found by trying every bag that could have produced it. Returns
+    /// `None` if even the minimum bag holds more than `max_total` cubes.
+    pub fn most_likely_bag(&self, max_total: u32) -> Option<(Bag, f64)> {
+        let min = self.min_bag();
+        let mut best: Option<(Bag, f64)> = None;
+
+        for red in min.red..=max_total {
+            for green in min.green..=max_total.saturating_sub(red) {
+                for blue in min.blue..=max_total.saturating_sub(red + green) {
+                    let bag = Bag { red, green, blue };
+                    let l = self.likelihood(&bag);
+                    if best.as_ref().is_none_or(|(_, b)| l > *b) {
+                        best = Some((bag, l));
+                    }
+                }
+            }
+        }
+
+        best
+    }
+}
+
+/// The possible game whose rounds are least likely under `bag`, scored by
+/// mean log-likelihood per round so long games aren't penalised for length.
+pub fn most_surprising<'a>(games: &'a [Game], bag: &Bag) -> Option<(&'a Game, f64)> {
+    games
+        .iter()
+        .map(|g| (g, g.likelihood(bag) / g.rounds.len() as f64))
+        .filter(|(_, l)| l.is_finite())
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ln_choose, most_surprising};
+    use crate::{parse, Bag};
+
+    fn bag(red: u32, green: u32, blue: u32) -> Bag {
+        Bag { red, green, blue }
+    }
+
+    #[test]
+    fn test_ln_choose() {
+        assert_eq!(ln_choose(5, 0), Some(0.0));
+        assert_eq!(ln_choose(3, 4), None);
+        assert!((ln_choose(5, 2).unwrap() - 10f64.ln()).abs() < 1e-12);
+        assert!((ln_choose(52, 5).unwrap() - 2598960f64.ln()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_likelihood() {
+        let games = parse("Game 1: 1 red, 1 blue; 2 green").unwrap();
+
+        // 2 of 5 cubes: 1 red and 1 blue is 2 * 1 / 10, 2 green is 1 / 10.
+        let l = games[0].round_likelihoods(&bag(2, 2, 1));
+        assert!((l[0] - (2.0f64 / 10.0).ln()).abs() < 1e-12);
+        assert!((l[1] - (1.0f64 / 10.0).ln()).abs() < 1e-12);
+
+        assert_eq!(games[0].likelihood(&bag(1, 1, 0)), f64::NEG_INFINITY);
+    }
+
+    #[test]
+    fn test_most_likely_bag() {
+        let games = parse("Game 1: 1 red, 1 blue; 1 red, 1 blue").unwrap();
+        let (b, l) = games[0].most_likely_bag(10).unwrap();
+        assert_eq!(b, bag(1, 0, 1));
+        assert_eq!(l, 0.0);
+
+        assert_eq!(games[0].most_likely_bag(1), None);
+    }
+
+    #[test]
+    fn test_most_surprising() {
+        let games = parse(
+            "Game 1: 1 red; 1 green; 1 blue
+Game 2: 3 red, 3 green
+Game 3: 20 red",
+        )
+        .unwrap();
+        let (g, _) = most_surprising(&games, &bag(12, 13, 14)).unwrap();
+        assert_eq!(g.id, 2);
+    }
+}
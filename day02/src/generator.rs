@@ -0,0 +1,108 @@
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+
+use crate::parser::{Game, Show};
+use crate::COLOURS;
+
+/// The shape of each game. The defaults match the puzzle input.
+#[derive(Clone, Debug)]
+pub struct Params {
+    /// Most rounds in a game.
+    pub rounds: usize,
+    /// Most distinct colours shown in a round, up to three.
+    pub colours: usize,
+    /// Most cubes of one colour shown at once.
+    pub cubes: u32,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params {
+            rounds: 6,
+            colours: 3,
+            cubes: 20,
+        }
+    }
+}
+
+/// A game of one to `rounds` rounds, each showing one to `colours` distinct
+/// colours of up to `cubes` cubes.
+pub fn random_game(rng: &mut impl Rng, id: u32, params: &Params) -> Game {
+    let rounds = (0..rng.gen_range(1..=params.rounds.max(1)))
+        .map(|_| {
+            let mut colours = COLOURS.clone();
+            colours.shuffle(rng);
+            colours[..rng.gen_range(1..=params.colours.clamp(1, 3))]
+                .iter()
+                .map(|colour| Show {
+                    number: rng.gen_range(1..=params.cubes.max(1)),
+                    colour: colour.clone(),
+                })
+                .collect()
+        })
+        .collect();
+
+    Game { id, rounds }
+}
+
+/// `count` games numbered from 1; the same seed always gives the same games.
+pub fn random_games(seed: u64, count: u32) -> Vec<Game> {
+    games(seed, count, &Params::default())
+}
+
+/// As `random_games`, with games of the given shape.
+pub fn games(seed: u64, count: u32, params: &Params) -> Vec<Game> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    (1..=count)
+        .map(|id| random_game(&mut rng, id, params))
+        .collect()
+}
+
+/// `size` games as puzzle input, or an error if there are too many to number
+/// with a `u32`.
+pub fn generate(seed: u64, size: usize, params: &Params) -> Result<String, String> {
+    let count = u32::try_from(size).map_err(|_| format!("{size} games are too many to number"))?;
+    Ok(games(seed, count, params)
+        .iter()
+        .map(|game| format!("{game}\n"))
+        .collect())
+}
+
+#[cfg(test)]
+mod test {
+    use super::{generate, random_games, Params};
+    use crate::{parse, stats};
+
+    #[test]
+    fn test_random_games() {
+        let games = random_games(2023, 100);
+        assert_eq!(games.len(), 100);
+        assert_eq!(games, random_games(2023, 100));
+        assert_ne!(games, random_games(2024, 100));
+
+        let text = games
+            .iter()
+            .map(|g| g.to_string())
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert_eq!(parse(&text), Ok(games));
+    }
+
+    #[test]
+    fn test_generate() {
+        let params = Params {
+            rounds: 2,
+            colours: 1,
+            cubes: 100,
+        };
+        let input = generate(5, 50, &params).unwrap();
+        assert_eq!(Ok(input.clone()), generate(5, 50, &params));
+
+        let games = parse(&input).unwrap();
+        assert_eq!(games.len(), 50);
+        assert!(games.iter().all(|g| g.rounds.len() <= 2));
+        assert!(games.iter().flat_map(|g| &g.rounds).all(|r| r.len() == 1));
+        assert!(stats(&games).max.red <= 100);
+
+        assert!(generate(5, u32::MAX as usize + 1, &params).is_err());
+    }
+}
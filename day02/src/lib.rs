@@ -0,0 +1,218 @@
+pub mod analysis;
+pub mod generator;
+pub mod parser;
+
+pub use parser::{parse_games, Colour, Game, Show};
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+pub static INPUT: &str = include_str!("../input.txt");
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Bag {
+    pub red: u32,
+    pub green: u32,
+    pub blue: u32,
+}
+
+impl Bag {
+    pub fn get(&self, c: &Colour) -> u32 {
+        match c {
+            Colour::Red => self.red,
+            Colour::Green => self.green,
+            Colour::Blue => self.blue,
+        }
+    }
+
+    pub fn get_mut(&mut self, c: &Colour) -> &mut u32 {
+        match c {
+            Colour::Red => &mut self.red,
+            Colour::Green => &mut self.green,
+            Colour::Blue => &mut self.blue,
+        }
+    }
+
+    pub fn contains(&self, other: &Bag) -> bool {
+        other.red <= self.red && other.green <= self.green && other.blue <= self.blue
+    }
+
+    /// Wide enough for any three cube counts.
+    pub fn power(&self) -> u128 {
+        self.red as u128 * self.green as u128 * self.blue as u128
+    }
+}
+
+impl Game {
+    pub fn shows(&self) -> impl Iterator<Item = &Show> {
+        self.rounds.iter().flatten()
+    }
+
+    pub fn min_bag(&self) -> Bag {
+        self.shows().fold(Bag::default(), |mut bag, show| {
+            let n = bag.get_mut(&show.colour);
+            *n = (*n).max(show.number);
+            bag
+        })
+    }
+}
+
+static COLOURS: [Colour; 3] = [Colour::Red, Colour::Green, Colour::Blue];
+
+#[derive(Debug, PartialEq)]
+pub struct Stats {
+    pub max: Bag,
+    pub mean_draw: f64,
+    pub most_common: Option<Colour>,
+}
+
+/// Aggregates over every round of every game. A draw is a single round, so
+/// `mean_draw` is the mean number of cubes shown per round, and
+/// `most_common` is the colour with the most cubes shown overall.
+pub fn stats(games: &[Game]) -> Stats {
+    let mut max = Bag::default();
+    let mut rounds = 0;
+
+    for game in games {
+        rounds += game.rounds.len();
+        for show in game.shows() {
+            let m = max.get_mut(&show.colour);
+            *m = (*m).max(show.number);
+        }
+    }
+
+    // Counted in `u64`s, as the totals can outgrow a `Bag`.
+    let totals = COLOURS.clone().map(|c| {
+        let shown = games.iter().flat_map(Game::shows).filter(|s| s.colour == c);
+        let total = shown.map(|s| s.number as u64).sum::<u64>();
+        (c, total)
+    });
+    let drawn: u64 = totals.iter().map(|(_, total)| total).sum();
+    let mean_draw = if rounds == 0 {
+        0.0
+    } else {
+        drawn as f64 / rounds as f64
+    };
+    let most_common = if drawn == 0 {
+        None
+    } else {
+        totals
+            .into_iter()
+            .max_by_key(|(_, total)| *total)
+            .map(|(c, _)| c)
+    };
+
+    Stats {
+        max,
+        mean_draw,
+        most_common,
+    }
+}
+
+/// The games in `input`, or an error showing the line it stopped making
+/// sense on.
+pub fn parse(input: &str) -> Result<Vec<Game>, String> {
+    let (_, games) = parse_games(input)
+        .map_err(|e| e.map_input(|i| i.lines().next().unwrap_or("")).to_string())?;
+    Ok(games)
+}
+
+pub static LIMIT: Bag = Bag {
+    red: 12,
+    green: 13,
+    blue: 14,
+};
+
+/// Summed as a `u64`, 32 bits more than any one id needs, so it would take
+/// more than `u32::MAX` games to overflow.
+pub fn part1(games: &[Game]) -> u64 {
+    games
+        .iter()
+        .filter(|g| LIMIT.contains(&g.min_bag()))
+        .map(|g| g.id as u64)
+        .sum()
+}
+
+/// Summed as a `u128`, 32 bits more than any one power needs, so it would
+/// take more than `u32::MAX` games to overflow.
+pub fn part2(games: &[Game]) -> u128 {
+    games.iter().map(|game| game.min_bag().power()).sum()
+}
+
+#[cfg(feature = "parallel")]
+pub fn par_part1(games: &[Game]) -> u64 {
+    games
+        .par_iter()
+        .filter(|g| LIMIT.contains(&g.min_bag()))
+        .map(|g| g.id as u64)
+        .sum()
+}
+
+#[cfg(feature = "parallel")]
+pub fn par_part2(games: &[Game]) -> u128 {
+    games.par_iter().map(|game| game.min_bag().power()).sum()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{parse, part1, part2, stats, Bag, Colour};
+
+    static TEST_INPUT: &str = include_str!("../tests/fixtures/example.txt");
+
+    harness::fixtures!(|input| parse(input).map(|games| part1(&games)), |input| {
+        parse(input).map(|games| part2(&games))
+    });
+
+    #[test]
+    fn test_min_bag() {
+        let games = parse(TEST_INPUT).unwrap();
+
+        assert_eq!(
+            games[0].min_bag(),
+            Bag {
+                red: 4,
+                green: 2,
+                blue: 6
+            }
+        );
+        assert_eq!(
+            games[2].min_bag(),
+            Bag {
+                red: 20,
+                green: 13,
+                blue: 6
+            }
+        );
+    }
+
+    #[test]
+    fn test_stats() {
+        let stats = stats(&parse(TEST_INPUT).unwrap());
+
+        assert_eq!(
+            stats.max,
+            Bag {
+                red: 20,
+                green: 13,
+                blue: 15
+            }
+        );
+        assert_eq!(stats.mean_draw, 159.0 / 14.0);
+        assert_eq!(stats.most_common, Some(Colour::Red));
+
+        // The red total is past `u32::MAX`.
+        let stats = crate::stats(
+            &parse("Game 1: 4294967295 red; 4294967295 red; 4294967295 blue, 1 green").unwrap(),
+        );
+        assert_eq!(stats.mean_draw, (3.0 * 4294967295.0 + 1.0) / 3.0);
+        assert_eq!(stats.most_common, Some(Colour::Red));
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_par_parts() {
+        let games = crate::generator::random_games(2023, 10_000);
+        assert_eq!(crate::par_part1(&games), part1(&games));
+        assert_eq!(crate::par_part2(&games), part2(&games));
+    }
+}
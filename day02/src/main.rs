@@ -1,80 +1,54 @@
-mod parser;
-
-use parser::{parse_games, Colour, Game};
-
-static INPUT: &str = include_str!("../input.txt");
-
-impl Game {
-    fn min_cubes(&self, c: Colour) -> u32 {
-        self.rounds
-            .iter()
-            .flat_map(|round| {
-                round.iter().filter_map(|show| {
-                    if show.colour == c {
-                        Some(show.number)
-                    } else {
-                        None
-                    }
-                })
-            })
-            .max()
-            .unwrap_or(0)
-    }
-}
-
-fn part1(input: &str) -> u32 {
-    let (_, games) = parse_games(input).unwrap();
-
-    games
-        .iter()
-        .filter(|g| {
-            g.min_cubes(Colour::Red) <= 12
-                && g.min_cubes(Colour::Green) <= 13
-                && g.min_cubes(Colour::Blue) <= 14
-        })
-        .map(|g| g.id)
-        .sum()
-}
-
-fn part2(input: &str) -> u32 {
-    let (_, games) = parse_games(input).unwrap();
-
-    let colours = [Colour::Red, Colour::Green, Colour::Blue];
-
-    games
-        .iter()
-        .map(|game| {
-            colours
-                .clone()
-                .map(|c| game.min_cubes(c))
-                .iter()
-                .product::<u32>()
-        })
-        .sum()
-}
+use day02::{analysis, generator, parse, stats, INPUT, LIMIT};
 
 fn main() {
-    println!("Day 2, part I:  {}", part1(INPUT));
-    println!("Day 2, part II: {}", part2(INPUT));
-}
-
-#[cfg(test)]
-mod test {
-    use crate::{part1, part2};
-
-    static TEST_INPUT: &str = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
-Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
-Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
-Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
-Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
+    let args: Vec<String> = std::env::args().collect();
+
+    if args.get(1).map(String::as_str) == Some("generate") {
+        let count = args.get(2).and_then(|n| n.parse().ok()).unwrap_or(100);
+        let seed = args.get(3).and_then(|n| n.parse().ok()).unwrap_or(0);
+        for game in generator::random_games(seed, count) {
+            println!("{}", game);
+        }
+        return;
+    }
 
-    #[test]
-    fn test_part1() {
-        assert_eq!(part1(TEST_INPUT), 8);
+    let games = match parse(INPUT) {
+        Ok(games) => games,
+        Err(e) => {
+            eprintln!("{e}");
+            return;
+        }
+    };
+
+    #[cfg(not(feature = "parallel"))]
+    let (part1, part2) = (day02::part1, day02::part2);
+    #[cfg(feature = "parallel")]
+    let (part1, part2) = (day02::par_part1, day02::par_part2);
+
+    println!("Day 2, part I:  {}", part1(&games));
+    println!("Day 2, part II: {}", part2(&games));
+
+    if args.iter().any(|a| a == "stats") {
+        let stats = stats(&games);
+        println!(
+            "Max: {} red, {} green, {} blue",
+            stats.max.red, stats.max.green, stats.max.blue
+        );
+        println!("Mean draw: {:.2}", stats.mean_draw);
+        if let Some(c) = stats.most_common {
+            println!("Most common: {:?}", c);
+        }
     }
 
-    #[test]
-    fn test_part2() {
-        assert_eq!(part2(TEST_INPUT), 2286);
+    if args.iter().any(|a| a == "likelihood") {
+        if let Some((game, l)) = analysis::most_surprising(&games, &LIMIT) {
+            println!("Most surprising: game {} ({:.2} per round)", game.id, l);
+            if let Some((bag, l)) = game.most_likely_bag(LIMIT.total()) {
+                println!(
+                    "Most likely bag: {} red, {} green, {} blue ({:.2})",
+                    bag.red, bag.green, bag.blue, l
+                );
+            }
+        }
     }
 }
@@ -1,3 +1,5 @@
+use std::fmt;
+
 use nom::{
     branch::alt,
     bytes::complete::tag,
@@ -8,6 +10,8 @@ use nom::{
     IResult,
 };
 
+/// A game as parsed, with at least one round, each showing at least one
+/// colour. Only a game like that prints as text `parse_games` reads back.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Game {
     pub id: u32,
@@ -27,6 +31,40 @@ pub struct Show {
     pub colour: Colour,
 }
 
+impl fmt::Display for Colour {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Colour::Red => "red",
+            Colour::Green => "green",
+            Colour::Blue => "blue",
+        })
+    }
+}
+
+impl fmt::Display for Show {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.number, self.colour)
+    }
+}
+
+impl fmt::Display for Game {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Game {}: ", self.id)?;
+        for (r, round) in self.rounds.iter().enumerate() {
+            if r > 0 {
+                f.write_str("; ")?;
+            }
+            for (s, show) in round.iter().enumerate() {
+                if s > 0 {
+                    f.write_str(", ")?;
+                }
+                write!(f, "{}", show)?;
+            }
+        }
+        Ok(())
+    }
+}
+
 fn parse_colour(i: &str) -> IResult<&str, Colour> {
     alt((
         value(Colour::Red, tag("red")),
@@ -60,7 +98,56 @@ pub fn parse_games(i: &str) -> IResult<&str, Vec<Game>> {
 
 #[cfg(test)]
 mod test {
-    use super::{parse_colour, parse_round, parse_show, Colour, Show};
+    use proptest::prelude::*;
+
+    use super::{parse_colour, parse_games, parse_round, parse_show, Colour, Game, Show};
+
+    fn colour() -> impl Strategy<Value = Colour> {
+        prop_oneof![Just(Colour::Red), Just(Colour::Green), Just(Colour::Blue)]
+    }
+
+    fn show() -> impl Strategy<Value = Show> {
+        (any::<u32>(), colour()).prop_map(|(number, colour)| Show { number, colour })
+    }
+
+    fn game() -> impl Strategy<Value = Game> {
+        (
+            any::<u32>(),
+            prop::collection::vec(prop::collection::vec(show(), 1..5), 1..8),
+        )
+            .prop_map(|(id, rounds)| Game { id, rounds })
+    }
+
+    proptest! {
+        #[test]
+        fn test_round_trip(games in prop::collection::vec(game(), 1..10)) {
+            let text = games
+                .iter()
+                .map(|g| g.to_string())
+                .collect::<Vec<_>>()
+                .join("\n");
+            prop_assert_eq!(parse_games(&text), Ok(("", games)));
+        }
+    }
+
+    #[test]
+    fn test_display() {
+        let line = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green";
+        let (_, games) = parse_games(line).unwrap();
+        assert_eq!(games[0].to_string(), line);
+
+        // Games `parse_games` could never give don't round trip.
+        let mut game = games[0].clone();
+        game.rounds.push(vec![]);
+        assert!(game.to_string().ends_with("2 green; "));
+        assert_ne!(parse_games(&game.to_string()), Ok(("", vec![game])));
+
+        let empty = Game {
+            id: 1,
+            rounds: vec![],
+        };
+        assert!(parse_games(&empty.to_string()).is_err());
+    }
 
     #[test]
     fn test_parsers() {
@@ -0,0 +1,140 @@
+use crate::{Engine, Part, Positioned, Symbol};
+
+/// Things that touch, directly or through a chain of other things.
+#[derive(Default)]
+pub struct Cluster<'a> {
+    pub parts: Vec<&'a Part>,
+    pub symbols: Vec<&'a Symbol>,
+}
+
+impl Cluster<'_> {
+    pub fn sum(&self) -> u64 {
+        self.parts.iter().map(|p| p.number as u64).sum()
+    }
+}
+
+fn root(parents: &mut [usize], mut n: usize) -> usize {
+    while parents[n] != n {
+        parents[n] = parents[parents[n]];
+        n = parents[n];
+    }
+    n
+}
+
+impl Engine {
+    /// Connected components of parts and symbols under 8-adjacency, in the
+    /// order their first thing was read. Parts are numbered first and
+    /// symbols after them, so both can share one union-find.
+    pub fn clusters(&self) -> Vec<Cluster<'_>> {
+        let n_parts = self.parts.iter().len();
+        let positions = self
+            .parts
+            .iter()
+            .map(|p| p.position())
+            .chain(self.symbols.iter().map(|s| s.position()));
+        let mut parents: Vec<usize> = (0..n_parts + self.symbols.iter().len()).collect();
+
+        for (a, position) in positions.enumerate() {
+            for cell in position.surrounding(1) {
+                let touching = [
+                    self.parts.index(&cell),
+                    self.symbols.index(&cell).map(|s| n_parts + s),
+                ];
+                for b in touching.into_iter().flatten() {
+                    let (ra, rb) = (root(&mut parents, a), root(&mut parents, b));
+                    parents[ra.max(rb)] = ra.min(rb);
+                }
+            }
+        }
+
+        let mut clusters: Vec<Cluster> = vec![];
+        let mut slots = vec![None; parents.len()];
+        for n in 0..parents.len() {
+            let r = root(&mut parents, n);
+            let slot = *slots[r].get_or_insert_with(|| {
+                clusters.push(Cluster::default());
+                clusters.len() - 1
+            });
+            if n < n_parts {
+                clusters[slot].parts.push(&self.parts.indexed[n]);
+            } else {
+                clusters[slot]
+                    .symbols
+                    .push(&self.symbols.indexed[n - n_parts]);
+            }
+        }
+
+        clusters
+    }
+
+    /// The symbol with the most parts around it, the first one read on ties.
+    pub fn busiest_symbol(&self) -> Option<(&Symbol, usize)> {
+        self.symbols
+            .iter()
+            .map(|sy| (sy, self.surrounding_parts(sy).count()))
+            .fold(None, |best, (sy, n)| match best {
+                Some((_, m)) if m >= n => best,
+                _ => Some((sy, n)),
+            })
+    }
+
+    /// Parts next to more than one symbol, with those symbols.
+    pub fn shared_parts(&self) -> Vec<(&Part, Vec<&Symbol>)> {
+        self.parts
+            .iter()
+            .map(|p| (p, self.symbols.around(p.position).collect::<Vec<_>>()))
+            .filter(|(_, symbols)| symbols.len() > 1)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{test::TEST_INPUT, Engine};
+
+    #[test]
+    fn test_clusters() {
+        let e = Engine::load(TEST_INPUT);
+        let clusters = e.clusters();
+
+        let sums: Vec<_> = clusters.iter().map(|c| c.sum()).collect();
+        assert_eq!(sums, [467 + 35, 114, 633, 617, 58, 592, 755 + 598, 664]);
+
+        let symbols: Vec<String> = clusters
+            .iter()
+            .map(|c| c.symbols.iter().map(|s| s.symbol).collect())
+            .collect();
+        assert_eq!(symbols, ["*", "", "#", "*", "", "+", "*", "$"]);
+    }
+
+    #[test]
+    fn test_clusters_without_symbols() {
+        // Parts touching diagonally join up even with no symbol between them.
+        let e = Engine::load("12..\n..34\n....\n5...");
+        let sums: Vec<_> = e.clusters().iter().map(|c| c.sum()).collect();
+        assert_eq!(sums, [12 + 34, 5]);
+    }
+
+    #[test]
+    fn test_busiest_symbol() {
+        let e = Engine::load(TEST_INPUT);
+        let (sy, n) = e.busiest_symbol().unwrap();
+        assert_eq!((sy.symbol, sy.position.x, sy.position.y, n), ('*', 3, 1, 2));
+
+        let e = Engine::load("1.2\n.*.\n3.4");
+        assert_eq!(e.busiest_symbol().map(|(_, n)| n), Some(4));
+        assert!(Engine::load("123").busiest_symbol().is_none());
+    }
+
+    #[test]
+    fn test_shared_parts() {
+        assert!(Engine::load(TEST_INPUT).shared_parts().is_empty());
+
+        let e = Engine::load("*...#\n.123.\n..$..");
+        let shared = e.shared_parts();
+        assert_eq!(shared.len(), 1);
+        assert_eq!(shared[0].0.number, 123);
+        let symbols: String = shared[0].1.iter().map(|s| s.symbol).collect();
+        assert_eq!(symbols, "*#$");
+    }
+}
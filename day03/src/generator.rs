@@ -0,0 +1,96 @@
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+static SYMBOLS: [char; 10] = ['*', '#', '+', '$', '/', '@', '=', '%', '&', '-'];
+
+/// How crowded a schematic is. The defaults match the puzzle input.
+#[derive(Clone, Debug)]
+pub struct Params {
+    /// Percent of cells that start a one to three digit number.
+    pub density: u32,
+    /// Percent of cells that hold a symbol.
+    pub symbols: u32,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params {
+            density: 10,
+            symbols: 4,
+        }
+    }
+}
+
+/// A `width` by `height` schematic with roughly the density of the puzzle
+/// input: about one cell in ten starts a one to three digit number and one in
+/// twenty-five is a symbol. The same seed always gives the same schematic.
+pub fn random_schematic(seed: u64, width: usize, height: usize) -> String {
+    schematic(seed, width, height, &Params::default())
+}
+
+/// As `random_schematic`, with the given density.
+pub fn schematic(seed: u64, width: usize, height: usize, params: &Params) -> String {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut out = String::with_capacity((width + 1) * height);
+
+    for _ in 0..height {
+        let mut x = 0;
+        while x < width {
+            let roll = rng.gen_range(0..100);
+            if roll < params.density {
+                let digits = rng.gen_range(1..=3).min(width - x);
+                out.push(char::from(b'1' + rng.gen_range(0..9)));
+                for _ in 1..digits {
+                    out.push(char::from(b'0' + rng.gen_range(0..10)));
+                }
+                x += digits;
+                if x < width {
+                    out.push('.');
+                    x += 1;
+                }
+                continue;
+            }
+            out.push(if roll < params.density.saturating_add(params.symbols) {
+                SYMBOLS[rng.gen_range(0..SYMBOLS.len())]
+            } else {
+                '.'
+            });
+            x += 1;
+        }
+        out.push('\n');
+    }
+
+    out
+}
+
+/// A `size` by `size` schematic as puzzle input.
+pub fn generate(seed: u64, size: usize, params: &Params) -> String {
+    schematic(seed, size, size, params)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{generate, random_schematic, Params};
+
+    #[test]
+    fn test_random_schematic() {
+        let s = random_schematic(3, 40, 20);
+        assert_eq!(s, random_schematic(3, 40, 20));
+        assert_eq!(s.lines().count(), 20);
+        assert!(s.lines().all(|l| l.chars().count() == 40));
+    }
+
+    #[test]
+    fn test_generate() {
+        let sparse = generate(3, 50, &Params::default());
+        assert_eq!(sparse, random_schematic(3, 50, 50));
+
+        let params = Params {
+            density: 30,
+            symbols: 20,
+        };
+        let dense = generate(3, 50, &params);
+        assert_eq!(dense, generate(3, 50, &params));
+        let blanks = |s: &str| s.chars().filter(|c| *c == '.').count();
+        assert!(blanks(&dense) < blanks(&sparse));
+    }
+}
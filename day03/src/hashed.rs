@@ -0,0 +1,97 @@
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+};
+
+use crate::{Engine, Position, Positioned};
+
+/// Every cell of `position` grown by `border`, collected afresh for each
+/// lookup.
+fn surrounding(position: Position, border: usize) -> Vec<Position> {
+    let mut r = vec![];
+    for x in position.xs(border) {
+        for y in position.ys(border) {
+            r.push(Position { x, y, w: 1 })
+        }
+    }
+    r
+}
+
+/// The index `Things` used before its dense grid: a `HashMap` entry for
+/// every cell each thing covers. Kept to benchmark the grid against.
+pub struct Hashed<'a, T> {
+    indexed: Vec<&'a T>,
+    hashed: HashMap<Position, usize>,
+}
+
+impl<'a, T: Positioned + Eq + Hash> Hashed<'a, T> {
+    pub fn from(things: impl Iterator<Item = &'a T>) -> Self {
+        let mut indexed = vec![];
+        let mut hashed = HashMap::new();
+
+        for t in things {
+            for position in surrounding(t.position(), 0) {
+                hashed.insert(position, indexed.len());
+            }
+            indexed.push(t);
+        }
+
+        Self { indexed, hashed }
+    }
+
+    fn find(&self, position: &Position) -> Option<&'a T> {
+        self.hashed.get(position).map(|index| self.indexed[*index])
+    }
+
+    /// Each distinct thing touching `position` or its border.
+    pub fn around(&self, position: Position) -> HashSet<&'a T> {
+        surrounding(position, 1)
+            .iter()
+            .filter_map(|position| self.find(position))
+            .collect()
+    }
+}
+
+pub fn part1(input: &str) -> Result<u64, String> {
+    let e = Engine::try_load(input)?;
+    let symbols = Hashed::from(e.symbols.iter());
+
+    Ok(e.parts
+        .iter()
+        .filter(|part| !symbols.around(part.position).is_empty())
+        .map(|part| part.number as u64)
+        .sum())
+}
+
+pub fn part2(input: &str) -> Result<u128, String> {
+    let e = Engine::try_load(input)?;
+    let parts = Hashed::from(e.parts.iter());
+
+    Ok(e.symbols
+        .iter()
+        .filter(|sy| sy.symbol == '*')
+        .map(|sy| parts.around(sy.position))
+        .filter(|parts| parts.len() == 2)
+        .map(|parts| parts.iter().map(|p| p.number as u128).product::<u128>())
+        .sum())
+}
+
+#[cfg(test)]
+mod test {
+    use super::{part1, part2};
+    use crate::{generator::random_schematic, test::TEST_INPUT};
+
+    #[test]
+    fn test_parts() {
+        assert_eq!(
+            (part1(TEST_INPUT), part2(TEST_INPUT)),
+            (Ok(4361), Ok(467835))
+        );
+
+        for seed in 0..5 {
+            let input = random_schematic(seed, 60, 40);
+            assert_eq!(part1(&input), crate::part1(&input));
+            assert_eq!(part2(&input), crate::part2(&input));
+        }
+    }
+}
@@ -0,0 +1,392 @@
+pub mod clusters;
+pub mod generator;
+pub mod hashed;
+pub mod render;
+pub mod rules;
+pub mod stream;
+
+use std::{num::NonZeroU32, ops::Range, slice::Iter};
+
+use rules::Rule;
+
+pub static INPUT: &str = include_str!("../input.txt");
+
+/// The number a run of ASCII digits spells, or an error if it doesn't fit a
+/// `u32`.
+fn number(digits: &[char]) -> Result<u32, String> {
+    let digits = String::from_iter(digits);
+    digits
+        .parse()
+        .map_err(|_| format!("{digits} is too big for a part number"))
+}
+
+#[derive(Clone, Copy, Eq, Hash, PartialEq)]
+pub struct Position {
+    pub x: usize,
+    pub y: usize,
+    pub w: usize,
+}
+
+impl Position {
+    pub fn xs(&self, border: usize) -> Range<usize> {
+        (self.x.max(border) - border)..(self.x + self.w + border)
+    }
+    pub fn ys(&self, border: usize) -> Range<usize> {
+        (self.y.max(border) - border)..(self.y + 1 + border)
+    }
+
+    /// Single cells covering this position grown by `border`, row by row.
+    pub fn surrounding(self, border: usize) -> impl Iterator<Item = Position> {
+        self.ys(border)
+            .flat_map(move |y| self.xs(border).map(move |x| Position { x, y, w: 1 }))
+    }
+}
+
+#[derive(Clone, Copy, Eq, Hash, PartialEq)]
+pub struct Part {
+    pub number: u32,
+    pub position: Position,
+    pub length: usize,
+}
+
+#[derive(Clone, Copy, Eq, Hash, PartialEq)]
+pub struct Symbol {
+    pub symbol: char,
+    pub position: Position,
+}
+
+pub trait Positioned {
+    fn position(&self) -> Position;
+}
+
+impl Positioned for Part {
+    fn position(&self) -> Position {
+        self.position
+    }
+}
+
+impl Positioned for Symbol {
+    fn position(&self) -> Position {
+        self.position
+    }
+}
+
+/// Things on the schematic, with a dense grid mapping each cell to the index
+/// of the thing covering it. The grid covers every cell from the top left
+/// corner to the furthest thing at four bytes each, however sparse the
+/// schematic is, so a single long row costs its length times the number of
+/// rows.
+pub struct Things<T>
+where
+    T: Positioned,
+{
+    indexed: Vec<T>,
+    /// One more than the index, so an empty cell fits in the same four
+    /// bytes.
+    grid: Vec<Option<NonZeroU32>>,
+    width: usize,
+}
+
+impl<T: Positioned> Things<T> {
+    /// Fails if the grid can't be sized, or there are too many things to
+    /// number with a `u32`.
+    fn from(things: Vec<T>) -> Result<Self, String> {
+        let width = things
+            .iter()
+            .map(|t| t.position().x + t.position().w)
+            .max()
+            .unwrap_or(0);
+        let height = things.iter().map(|t| t.position().y + 1).max().unwrap_or(0);
+
+        let cells = width
+            .checked_mul(height)
+            .ok_or_else(|| format!("a {width} by {height} schematic is too big to index"))?;
+        let mut grid = vec![None; cells];
+        for (index, t) in things.iter().enumerate() {
+            let index = u32::try_from(index + 1)
+                .ok()
+                .and_then(NonZeroU32::new)
+                .ok_or_else(|| format!("{} things are too many to index", things.len()))?;
+            for position in t.position().surrounding(0) {
+                grid[position.y * width + position.x] = Some(index);
+            }
+        }
+
+        Ok(Self {
+            indexed: things,
+            grid,
+            width,
+        })
+    }
+
+    pub fn index(&self, position: &Position) -> Option<usize> {
+        if position.x >= self.width {
+            return None;
+        }
+        self.grid
+            .get(position.y * self.width + position.x)
+            .copied()
+            .flatten()
+            .map(|index| index.get() as usize - 1)
+    }
+
+    /// Each distinct thing touching `position` or its border. Cells are
+    /// visited row by row, so a thing spanning several cells shows up as a
+    /// run of the same index and only needs comparing with the previous hit.
+    pub fn around(&self, position: Position) -> impl Iterator<Item = &T> {
+        let mut previous = None;
+        position
+            .surrounding(1)
+            .filter_map(|position| self.index(&position))
+            .filter(move |index| previous.replace(*index) != Some(*index))
+            .map(|index| &self.indexed[index])
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        self.indexed.iter()
+    }
+}
+
+pub struct Engine {
+    pub parts: Things<Part>,
+    pub symbols: Things<Symbol>,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl Engine {
+    /// Panics on anything `try_load` would fail on.
+    pub fn load(input: &str) -> Self {
+        Self::try_load(input).unwrap()
+    }
+
+    pub fn try_load(input: &str) -> Result<Self, String> {
+        Self::try_load_with_blanks(input, &['.'])
+    }
+
+    pub fn load_with_blanks(input: &str, blanks: &[char]) -> Self {
+        Self::try_load_with_blanks(input, blanks).unwrap()
+    }
+
+    /// Reads the schematic as a grid of characters, so every coordinate is a
+    /// char column whatever the encoding width of the characters before it.
+    /// ASCII digits make up part numbers, anything in `blanks` is empty
+    /// space, and every other character is a symbol. A part number too big
+    /// for a `u32` is an error, as is a schematic too big to index; see
+    /// `Things` for what the index costs.
+    pub fn try_load_with_blanks(input: &str, blanks: &[char]) -> Result<Self, String> {
+        let mut ps = vec![];
+        let mut ss = vec![];
+        let mut width = 0;
+        let mut height = 0;
+
+        for (y, line) in input.lines().enumerate() {
+            let row: Vec<char> = line.chars().collect();
+            let mut x = 0;
+            width = width.max(row.len());
+            height = y + 1;
+
+            while x < row.len() {
+                if row[x].is_ascii_digit() {
+                    let start = x;
+                    while x < row.len() && row[x].is_ascii_digit() {
+                        x += 1;
+                    }
+                    let length = x - start;
+                    ps.push(Part {
+                        number: number(&row[start..x])?,
+                        position: Position {
+                            x: start,
+                            y,
+                            w: length,
+                        },
+                        length,
+                    });
+                    continue;
+                }
+
+                if !blanks.contains(&row[x]) {
+                    ss.push(Symbol {
+                        symbol: row[x],
+                        position: Position { x, y, w: 1 },
+                    });
+                }
+
+                x += 1;
+            }
+        }
+
+        Ok(Engine {
+            parts: Things::from(ps)?,
+            symbols: Things::from(ss)?,
+            width,
+            height,
+        })
+    }
+
+    /// The distinct non-ASCII characters that were read as symbols.
+    pub fn non_ascii_symbols(&self) -> Vec<char> {
+        let mut found: Vec<char> = self
+            .symbols
+            .iter()
+            .map(|s| s.symbol)
+            .filter(|c| !c.is_ascii())
+            .collect();
+        found.sort();
+        found.dedup();
+        found
+    }
+
+    pub fn surrounding_symbols(&self, part: &Part) -> Option<&Symbol> {
+        self.symbols.around(part.position).next()
+    }
+
+    pub fn surrounding_parts(&self, s: &Symbol) -> impl Iterator<Item = &Part> {
+        self.parts.around(s.position)
+    }
+}
+
+/// Summed as a `u64`, which would take more than `u32::MAX` part numbers to
+/// overflow.
+pub fn part1(input: &str) -> Result<u64, String> {
+    let e = Engine::try_load(input)?;
+
+    Ok(e.parts
+        .iter()
+        .filter_map(|part| e.surrounding_symbols(part).map(|_| part.number as u64))
+        .sum())
+}
+
+pub fn part2(input: &str) -> Result<u128, String> {
+    Rule::gear().apply(&Engine::try_load(input)?)
+}
+
+#[cfg(test)]
+mod test {
+    use proptest::prelude::*;
+
+    pub static TEST_INPUT: &str = include_str!("../tests/fixtures/example.txt");
+
+    harness::fixtures!(crate::part1, crate::part2);
+
+    /// Both answers straight from the character grid: every number with a
+    /// symbol in the box around it, and every `*` with exactly two numbers
+    /// in its box.
+    fn reference(input: &str) -> (u64, u128) {
+        let grid: Vec<Vec<char>> = input.lines().map(|line| line.chars().collect()).collect();
+        let at = |x: usize, y: usize| grid.get(y).and_then(|row| row.get(x)).copied();
+        let is_symbol = |c: char| c != '.' && !c.is_ascii_digit();
+
+        // (y, first column, last column, value)
+        let mut numbers = vec![];
+        for (y, row) in grid.iter().enumerate() {
+            let mut x = 0;
+            while x < row.len() {
+                let start = x;
+                while x < row.len() && row[x].is_ascii_digit() {
+                    x += 1;
+                }
+                if x > start {
+                    let value: String = row[start..x].iter().collect();
+                    numbers.push((y, start, x - 1, value.parse::<u64>().unwrap()));
+                } else {
+                    x += 1;
+                }
+            }
+        }
+
+        let touches = |(y, first, last, _): &(usize, usize, usize, u64), cx: usize, cy: usize| {
+            cy + 1 >= *y && cy <= y + 1 && cx + 1 >= *first && cx <= last + 1
+        };
+
+        let part1 = numbers
+            .iter()
+            .filter(|n| {
+                (n.0.saturating_sub(1)..=n.0 + 1).any(|y| {
+                    (n.1.saturating_sub(1)..=n.2 + 1).any(|x| at(x, y).is_some_and(is_symbol))
+                })
+            })
+            .map(|n| n.3)
+            .sum();
+
+        let mut part2 = 0;
+        for (y, row) in grid.iter().enumerate() {
+            for (x, c) in row.iter().enumerate() {
+                let around: Vec<u128> = numbers
+                    .iter()
+                    .filter(|n| touches(n, x, y))
+                    .map(|n| n.3 as u128)
+                    .collect();
+                if *c == '*' && around.len() == 2 {
+                    part2 += around[0] * around[1];
+                }
+            }
+        }
+
+        (part1, part2)
+    }
+
+    /// Rows of blanks, symbols and numbers, with every number ended by a
+    /// blank or a symbol so none grow too long for a `u32`.
+    fn schematic() -> impl Strategy<Value = String> {
+        let symbol = "[*#+$/=%@&-]";
+        let token = prop_oneof![
+            4 => Just(".".to_string()),
+            1 => symbol,
+            2 => (0..1000u32, prop_oneof![Just(".".to_string()), symbol])
+                .prop_map(|(n, end)| format!("{n}{end}")),
+        ];
+        let row = prop::collection::vec(token, 0..8).prop_map(|tokens| tokens.concat());
+        prop::collection::vec(row, 1..8).prop_map(|rows| rows.join("\n"))
+    }
+
+    proptest! {
+        #[test]
+        fn test_reference(input in schematic()) {
+            let (part1, part2) = reference(&input);
+            prop_assert_eq!(crate::part1(&input), Ok(part1));
+            prop_assert_eq!(crate::part2(&input), Ok(part2));
+            prop_assert_eq!(crate::stream::part1(&input), Ok(part1));
+            prop_assert_eq!(crate::stream::part2(&input), Ok(part2));
+        }
+    }
+
+    #[test]
+    fn test_non_ascii() {
+        // Each of these is one column wide, but two or three bytes long.
+        let input = "é12..34\n..£....\n58....€";
+        let e = crate::Engine::load(input);
+
+        let positions: Vec<_> = e.parts.iter().map(|p| (p.number, p.position.x)).collect();
+        assert_eq!(positions, [(12, 1), (34, 5), (58, 0)]);
+
+        assert_eq!(crate::part1(input), Ok(12 + 58));
+        assert_eq!(e.non_ascii_symbols(), ['£', 'é', '€']);
+    }
+
+    #[test]
+    fn test_blanks() {
+        let input = "12~\n~~#\n3 .";
+        assert_eq!(crate::part1(input), Ok(12 + 3));
+
+        let e = crate::Engine::load_with_blanks(input, &['~', ' ', '.']);
+        let parts = e
+            .parts
+            .iter()
+            .filter(|p| e.surrounding_symbols(p).is_some());
+        assert_eq!(parts.map(|p| p.number).collect::<Vec<_>>(), [12]);
+    }
+
+    #[test]
+    fn test_index_size() {
+        // Each grid only reaches as far as its furthest thing.
+        let e = crate::Engine::load("..1\n\n\n*");
+        assert_eq!((e.parts.grid.len(), e.symbols.grid.len()), (3, 4));
+        assert_eq!(std::mem::size_of_val(&e.parts.grid[0]), 4);
+        assert_eq!(
+            e.parts.index(&crate::Position { x: 2, y: 0, w: 1 }),
+            Some(0)
+        );
+        assert_eq!(e.symbols.index(&crate::Position { x: 0, y: 2, w: 1 }), None);
+    }
+}
@@ -1,212 +1,136 @@
 use std::{
-    collections::{HashMap, HashSet},
-    ops::Range,
-    slice::Iter,
+    fmt::Display,
+    time::{Duration, Instant},
 };
 
-use regex::Regex;
+use day03::{generator, hashed, part1, part2, render, rules::Rule, stream, Engine, INPUT};
 
-static INPUT: &str = include_str!("../input.txt");
-
-#[derive(Clone, Copy, Eq, Hash, PartialEq)]
-struct Position {
-    x: usize,
-    y: usize,
-    w: usize,
+fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let out = f();
+    (out, start.elapsed())
 }
 
-impl Position {
-    fn xs(&self, border: usize) -> Range<usize> {
-        (self.x.max(border) - border)..(self.x + self.w + border)
-    }
-    fn ys(&self, border: usize) -> Range<usize> {
-        (self.y.max(border) - border)..(self.y + 1 + border)
+/// An answer as printed, or the error the part gave instead.
+fn shown(answer: Result<impl Display, String>) -> String {
+    match answer {
+        Ok(answer) => answer.to_string(),
+        Err(e) => format!("error: {e}"),
     }
 }
 
-#[derive(Clone, Copy, Eq, Hash, PartialEq)]
-struct Part {
-    number: u32,
-    position: Position,
-    length: usize,
-}
-
-#[derive(Clone, Copy, Eq, Hash, PartialEq)]
-struct Symbol {
-    symbol: char,
-    position: Position,
+/// Times both parts on a generated `size` by `size` schematic, with the
+/// dense grid index and then with the `HashMap` one it replaced.
+fn bench(size: usize) {
+    let input = generator::random_schematic(2023, size, size);
+
+    let (grid, grid_time) = timed(|| part1(&input));
+    let (old, old_time) = timed(|| hashed::part1(&input));
+    assert_eq!(grid, old, "the indexes disagree on part I");
+    println!(
+        "{size}x{size}, part I:  {}, grid {grid_time:?}, hashed {old_time:?} ({:.1}x)",
+        shown(grid),
+        old_time.as_secs_f64() / grid_time.as_secs_f64()
+    );
+
+    let (grid, grid_time) = timed(|| part2(&input));
+    let (old, old_time) = timed(|| hashed::part2(&input));
+    assert_eq!(grid, old, "the indexes disagree on part II");
+    println!(
+        "{size}x{size}, part II: {}, grid {grid_time:?}, hashed {old_time:?} ({:.1}x)",
+        shown(grid),
+        old_time.as_secs_f64() / grid_time.as_secs_f64()
+    );
 }
 
-trait Positioned {
-    fn position(&self) -> Position;
-
-    fn surrounding(&self, size: usize) -> Vec<Position> {
-        let mut r = vec![];
-        for x in self.position().xs(size) {
-            for y in self.position().ys(size) {
-                r.push(Position { x, y, w: 1 })
-            }
-        }
-        r
-    }
-}
-
-impl Positioned for Part {
-    fn position(&self) -> Position {
-        self.position
-    }
-}
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
 
-impl Positioned for Symbol {
-    fn position(&self) -> Position {
-        self.position
+    if args.get(1).map(String::as_str) == Some("bench") {
+        bench(args.get(2).and_then(|n| n.parse().ok()).unwrap_or(1000));
+        return;
     }
-}
-
-struct Things<T>
-where
-    T: Positioned,
-{
-    indexed: Vec<T>,
-    hashed: HashMap<Position, usize>,
-}
-
-impl<T: Positioned> Things<T> {
-    fn from(things: Vec<T>) -> Self {
-        let mut indexed = vec![];
-        let mut hashed = HashMap::new();
 
-        for t in things {
-            for position in t.surrounding(0) {
-                hashed.insert(position, indexed.len());
-            }
-            indexed.push(t);
+    if args.get(1).map(String::as_str) == Some("render") {
+        let e = Engine::load(INPUT);
+        match args.get(2).map(String::as_str).unwrap_or("ansi") {
+            "html" => print!("{}", render::html(&e)),
+            "svg" => print!("{}", render::svg(&e)),
+            _ => print!("{}", render::ansi(&e)),
         }
-
-        Self { indexed, hashed }
-    }
-
-    fn find(&self, position: &Position) -> Option<&T> {
-        self.hashed.get(position).map(|index| &self.indexed[*index])
+        for part in render::uncounted(&e) {
+            eprintln!(
+                "Uncounted: {} at ({}, {})",
+                part.number, part.position.x, part.position.y
+            );
+        }
+        return;
     }
 
-    fn iter(&self) -> Iter<'_, T> {
-        self.indexed.iter()
+    if args.get(1).map(String::as_str) == Some("clusters") {
+        let e = Engine::load(INPUT);
+        let clusters = e.clusters();
+        println!("Clusters: {}", clusters.len());
+        if let Some(c) = clusters.iter().max_by_key(|c| c.sum()) {
+            println!(
+                "Largest sum: {} ({} parts, {} symbols)",
+                c.sum(),
+                c.parts.len(),
+                c.symbols.len()
+            );
+        }
+        if let Some((sy, n)) = e.busiest_symbol() {
+            println!(
+                "Busiest symbol: {} at ({}, {}) with {} parts",
+                sy.symbol, sy.position.x, sy.position.y, n
+            );
+        }
+        for (part, symbols) in e.shared_parts() {
+            println!(
+                "Shared: {} at ({}, {}) by {}",
+                part.number,
+                part.position.x,
+                part.position.y,
+                String::from_iter(symbols.iter().map(|s| s.symbol))
+            );
+        }
+        return;
     }
-}
 
-struct Engine {
-    parts: Things<Part>,
-    symbols: Things<Symbol>,
-}
-
-impl Engine {
-    fn load(input: &str) -> Self {
-        let part_numbers_regex = Regex::new(r"\d+").unwrap();
-
-        let mut ps = vec![];
-        let mut ss = vec![];
-
-        for (y, line) in input.lines().enumerate() {
-            ps.extend(part_numbers_regex.find_iter(line).map(|m| {
-                let number = m.as_str().parse().unwrap();
-                let x = m.start();
-                let length = m.len();
-                Part {
-                    number,
-                    position: Position { x, y, w: length },
-                    length,
-                }
-            }));
-            for (x, c) in line.chars().enumerate() {
-                if !c.is_numeric() && c != '.' {
-                    ss.push(Symbol {
-                        symbol: c,
-                        position: Position { x, y, w: 1 },
-                    });
+    if args.get(1).map(String::as_str) == Some("stream") {
+        let (mut parts, mut gears) = (0u64, 0u128);
+        for event in stream::Stream::new(std::io::stdin().lock()) {
+            match event {
+                Ok(stream::Event::Part(n)) => parts += n as u64,
+                Ok(stream::Event::Gear(n)) => gears += n as u128,
+                Err(e) => {
+                    eprintln!("{e}");
+                    return;
                 }
             }
         }
-
-        Engine {
-            parts: Things::from(ps),
-            symbols: Things::from(ss),
-        }
+        println!("Day 03, part I:  {}", parts);
+        println!("Day 03, part II: {}", gears);
+        return;
     }
 
-    fn surrounding_things<'a, T: Positioned>(
-        &'a self,
-        a: &dyn Positioned,
-        bs: &'a Things<T>,
-    ) -> HashSet<&T>
-    where
-        T: std::hash::Hash,
-        T: Eq,
-        T: Copy,
-    {
-        a.surrounding(1)
-            .iter()
-            .filter_map(|position| bs.find(position))
-            .collect()
-    }
-
-    fn surrounding_symbols(&self, part: &Part) -> Option<&Symbol> {
-        self.surrounding_things(part, &self.symbols)
-            .into_iter()
-            .next()
+    if args.get(1).map(String::as_str) == Some("rule") {
+        match args.get(2).map(|spec| spec.parse::<Rule>()) {
+            Some(Ok(rule)) => {
+                let answer = rule.apply(&Engine::load(INPUT));
+                println!("Day 03, {}: {}", args[2], shown(answer));
+            }
+            Some(Err(e)) => eprintln!("{e}"),
+            None => eprintln!("usage: day03 rule symbols:count:aggregate"),
+        }
+        return;
     }
 
-    fn surrounding_parts(&self, s: &Symbol) -> HashSet<&Part> {
-        self.surrounding_things(s, &self.parts)
-    }
-}
-
-fn part1(input: &str) -> u32 {
-    let e = Engine::load(input);
-
-    e.parts
-        .iter()
-        .filter_map(|part| e.surrounding_symbols(part).map(|_| part.number))
-        .sum()
-}
-
-fn part2(input: &str) -> u32 {
-    let e = Engine::load(input);
-
-    e.symbols
-        .iter()
-        .filter(|sy| sy.symbol == '*')
-        .map(|sy| e.surrounding_parts(sy))
-        .filter(|parts| parts.len() == 2)
-        .map(|parts| parts.iter().map(|p| p.number).product::<u32>())
-        .sum()
-}
-
-fn main() {
-    println!("Day 03, part I:  {}", part1(INPUT));
-    println!("Day 03, part II: {}", part2(INPUT));
-}
-
-#[cfg(test)]
-mod test {
-    static TEST_INPUT: &str = "467..114..
-...*......
-..35..633.
-......#...
-617*......
-.....+.58.
-..592.....
-......755.
-...$.*....
-.664.598..";
-
-    #[test]
-    fn test_part1() {
-        assert_eq!(crate::part1(TEST_INPUT), 4361);
-    }
+    println!("Day 03, part I:  {}", shown(part1(INPUT)));
+    println!("Day 03, part II: {}", shown(part2(INPUT)));
 
-    #[test]
-    fn test_part2() {
-        assert_eq!(crate::part2(TEST_INPUT), 467835);
+    let symbols = Engine::load(INPUT).non_ascii_symbols();
+    if !symbols.is_empty() {
+        println!("Non-ASCII symbols: {}", String::from_iter(symbols));
     }
 }
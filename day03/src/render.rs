@@ -0,0 +1,228 @@
+use std::fmt::Write;
+
+use crate::{rules::Rule, Engine, Part};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Class {
+    Blank,
+    Counted,
+    Uncounted,
+    Gear,
+    Symbol,
+}
+
+impl Class {
+    fn ansi(self) -> &'static str {
+        match self {
+            Class::Blank => "\x1b[2m",
+            Class::Counted => "\x1b[32m",
+            Class::Uncounted => "\x1b[31m",
+            Class::Gear => "\x1b[1;33m",
+            Class::Symbol => "\x1b[36m",
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Class::Blank => "blank",
+            Class::Counted => "counted",
+            Class::Uncounted => "uncounted",
+            Class::Gear => "gear",
+            Class::Symbol => "symbol",
+        }
+    }
+
+    fn colour(self) -> &'static str {
+        match self {
+            Class::Blank => "#999",
+            Class::Counted => "#2a2",
+            Class::Uncounted => "#d22",
+            Class::Gear => "#c90",
+            Class::Symbol => "#29c",
+        }
+    }
+}
+
+/// The schematic rebuilt from the engine's parts and symbols, each cell
+/// tagged with how it was treated by the two parts of the puzzle. Blanks are
+/// drawn as `.` whatever they were in the input.
+pub fn cells(e: &Engine) -> Vec<Vec<(char, Class)>> {
+    let mut grid = vec![vec![('.', Class::Blank); e.width]; e.height];
+
+    let gear = Rule::gear();
+    for sy in e.symbols.iter() {
+        let class = if gear.matches(e, sy) {
+            Class::Gear
+        } else {
+            Class::Symbol
+        };
+        grid[sy.position.y][sy.position.x] = (sy.symbol, class);
+    }
+
+    for part in e.parts.iter() {
+        let class = if e.surrounding_symbols(part).is_some() {
+            Class::Counted
+        } else {
+            Class::Uncounted
+        };
+        let digits = format!("{:0width$}", part.number, width = part.length);
+        for (i, c) in digits.chars().enumerate() {
+            grid[part.position.y][part.position.x + i] = (c, class);
+        }
+    }
+
+    grid
+}
+
+/// Parts not next to any symbol, so left out of part I.
+pub fn uncounted(e: &Engine) -> Vec<&Part> {
+    e.parts
+        .iter()
+        .filter(|part| e.surrounding_symbols(part).is_none())
+        .collect()
+}
+
+/// Writes each run of same-class cells with `open` before it and `close`
+/// after it, one line per row.
+fn render(
+    e: &Engine,
+    open: impl Fn(Class) -> String,
+    close: &str,
+    escape: impl Fn(char) -> String,
+) -> Vec<String> {
+    cells(e)
+        .iter()
+        .map(|row| {
+            let mut line = String::new();
+            for run in row.chunk_by(|a, b| a.1 == b.1) {
+                line.push_str(&open(run[0].1));
+                line.extend(run.iter().map(|(c, _)| escape(*c)));
+                line.push_str(close);
+            }
+            line
+        })
+        .collect()
+}
+
+fn escape_xml(c: char) -> String {
+    match c {
+        '&' => "&amp;".to_string(),
+        '<' => "&lt;".to_string(),
+        '>' => "&gt;".to_string(),
+        _ => c.to_string(),
+    }
+}
+
+pub fn ansi(e: &Engine) -> String {
+    let lines = render(
+        e,
+        |class| class.ansi().to_string(),
+        "\x1b[0m",
+        |c| c.to_string(),
+    );
+    lines.join("\n") + "\n"
+}
+
+pub fn html(e: &Engine) -> String {
+    let mut out = String::from("<!DOCTYPE html>\n<style>\n");
+    for class in [
+        Class::Blank,
+        Class::Counted,
+        Class::Uncounted,
+        Class::Gear,
+        Class::Symbol,
+    ] {
+        writeln!(out, ".{} {{ color: {} }}", class.name(), class.colour()).unwrap();
+    }
+    out.push_str("</style>\n<pre>\n");
+
+    let lines = render(
+        e,
+        |class| format!("<span class=\"{}\">", class.name()),
+        "</span>",
+        escape_xml,
+    );
+    for line in lines {
+        out.push_str(&line);
+        out.push('\n');
+    }
+
+    out.push_str("</pre>\n");
+    out
+}
+
+pub fn svg(e: &Engine) -> String {
+    let lines = render(
+        e,
+        |class| format!("<tspan fill=\"{}\">", class.colour()),
+        "</tspan>",
+        escape_xml,
+    );
+
+    let mut out = String::new();
+    writeln!(
+        out,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" \
+         font-family=\"monospace\" font-size=\"14\" xml:space=\"preserve\">",
+        e.width * 9,
+        lines.len() * 16,
+    )
+    .unwrap();
+    for (y, line) in lines.iter().enumerate() {
+        writeln!(out, "<text x=\"0\" y=\"{}\">{}</text>", (y + 1) * 16, line).unwrap();
+    }
+    out.push_str("</svg>\n");
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ansi, cells, html, svg, uncounted, Class};
+    use crate::{test::TEST_INPUT, Engine};
+
+    #[test]
+    fn test_cells() {
+        let e = Engine::load(TEST_INPUT);
+        let grid = cells(&e);
+
+        assert_eq!(grid.len(), 10);
+        assert_eq!(grid[0][0], ('4', Class::Counted));
+        assert_eq!(grid[0][5], ('1', Class::Uncounted));
+        assert_eq!(grid[1][3], ('*', Class::Gear));
+        assert_eq!(grid[3][6], ('#', Class::Symbol));
+        assert_eq!(grid[4][3], ('*', Class::Symbol));
+        assert_eq!(grid[0][3], ('.', Class::Blank));
+
+        let rebuilt: Vec<String> = grid
+            .iter()
+            .map(|row| row.iter().map(|(c, _)| c).collect())
+            .collect();
+        assert_eq!(rebuilt.join("\n"), TEST_INPUT.trim_end());
+    }
+
+    #[test]
+    fn test_uncounted() {
+        let e = Engine::load(TEST_INPUT);
+        let found: Vec<_> = uncounted(&e)
+            .iter()
+            .map(|p| (p.number, p.position.x, p.position.y))
+            .collect();
+        assert_eq!(found, [(114, 5, 0), (58, 7, 5)]);
+    }
+
+    #[test]
+    fn test_exports() {
+        let e = Engine::load("1<.\n..2");
+        assert_eq!(cells(&Engine::load("007"))[0][0], ('0', Class::Uncounted));
+
+        assert_eq!(
+            ansi(&e),
+            "\x1b[32m1\x1b[0m\x1b[36m<\x1b[0m\x1b[2m.\x1b[0m\n\
+             \x1b[2m..\x1b[0m\x1b[32m2\x1b[0m\n"
+        );
+        assert!(
+            html(&e).contains("<span class=\"counted\">1</span><span class=\"symbol\">&lt;</span>")
+        );
+        assert!(svg(&e).contains("<text x=\"0\" y=\"32\"><tspan fill=\"#999\">..</tspan>"));
+    }
+}
@@ -0,0 +1,190 @@
+use std::str::FromStr;
+
+use crate::{Engine, Symbol};
+
+/// How many parts must touch a symbol for it to count.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Count {
+    pub min: usize,
+    pub max: Option<usize>,
+}
+
+impl Count {
+    fn contains(&self, n: usize) -> bool {
+        n >= self.min && self.max.is_none_or(|max| n <= max)
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Aggregate {
+    Product,
+    Sum,
+    Max,
+}
+
+/// Which symbols count, how many parts they need, and how to combine the
+/// numbers of those parts. A rule's answer is the sum over every counted
+/// symbol, e.g. part II is `*` symbols with exactly two parts, multiplied.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Rule {
+    /// `None` means every symbol.
+    pub symbols: Option<Vec<char>>,
+    pub count: Count,
+    pub aggregate: Aggregate,
+}
+
+impl Rule {
+    pub fn gear() -> Self {
+        Rule {
+            symbols: Some(vec!['*']),
+            count: Count {
+                min: 2,
+                max: Some(2),
+            },
+            aggregate: Aggregate::Product,
+        }
+    }
+
+    /// Whether `sy` is one of this rule's symbols with the right number of
+    /// parts around it.
+    pub fn matches(&self, e: &Engine, sy: &Symbol) -> bool {
+        self.symbols.as_ref().is_none_or(|s| s.contains(&sy.symbol))
+            && self.count.contains(e.surrounding_parts(sy).count())
+    }
+
+    /// The sum over every counted symbol, or an error if it won't fit in a
+    /// `u128`, as a product of many parts can easily overflow.
+    pub fn apply(&self, e: &Engine) -> Result<u128, String> {
+        e.symbols
+            .iter()
+            .filter(|sy| self.matches(e, sy))
+            .try_fold(0u128, |total, sy| {
+                let mut numbers = e.surrounding_parts(sy).map(|p| p.number as u128);
+                let value = match self.aggregate {
+                    Aggregate::Product => numbers.try_fold(1u128, |a, n| a.checked_mul(n)),
+                    Aggregate::Sum => numbers.try_fold(0u128, |a, n| a.checked_add(n)),
+                    Aggregate::Max => Some(numbers.max().unwrap_or(0)),
+                };
+                value.and_then(|v| total.checked_add(v))
+            })
+            .ok_or_else(|| "the answer is too big for a u128".to_string())
+    }
+}
+
+impl FromStr for Count {
+    type Err = String;
+
+    /// `2`, `>=2`, `<=2` or `2..=4`. A range that ends before it starts
+    /// would match nothing, so it's an error.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let n = |s: &str| {
+            s.parse::<usize>()
+                .map_err(|_| format!("invalid part count: {s}"))
+        };
+
+        if let Some(min) = s.strip_prefix(">=") {
+            Ok(Count {
+                min: n(min)?,
+                max: None,
+            })
+        } else if let Some(max) = s.strip_prefix("<=") {
+            Ok(Count {
+                min: 0,
+                max: Some(n(max)?),
+            })
+        } else if let Some((min, max)) = s.split_once("..=") {
+            let (min, max) = (n(min)?, n(max)?);
+            if min > max {
+                return Err(format!("empty part count: {s}"));
+            }
+            Ok(Count {
+                min,
+                max: Some(max),
+            })
+        } else {
+            let exact = n(s)?;
+            Ok(Count {
+                min: exact,
+                max: Some(exact),
+            })
+        }
+    }
+}
+
+impl FromStr for Aggregate {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "product" => Ok(Aggregate::Product),
+            "sum" => Ok(Aggregate::Sum),
+            "max" => Ok(Aggregate::Max),
+            _ => Err(format!("invalid aggregate: {s}")),
+        }
+    }
+}
+
+impl FromStr for Rule {
+    type Err = String;
+
+    /// `symbols:count:aggregate`, e.g. `*:2:product` for gears or
+    /// `any:>=1:sum` for every symbol. Symbols are listed without separators.
+    /// `:` separates the fields, so it can only be counted through `any`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let fields: Vec<&str> = s.split(':').collect();
+        let [symbols, count, aggregate] = fields[..] else {
+            return Err(format!("expected symbols:count:aggregate, got {s}"));
+        };
+
+        Ok(Rule {
+            symbols: match symbols {
+                "any" => None,
+                _ => Some(symbols.chars().collect()),
+            },
+            count: count.parse()?,
+            aggregate: aggregate.parse()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Rule;
+    use crate::{test::TEST_INPUT, Engine};
+
+    #[test]
+    fn test_rules() {
+        let e = Engine::load(TEST_INPUT);
+
+        let table = [
+            ("*:2:product", 467835),
+            ("*:2:sum", 467 + 35 + 755 + 598),
+            ("*:1:sum", 617),
+            ("*:>=1:max", 467 + 617 + 755),
+            ("any:>=1:sum", 4361),
+            ("#+$:1:sum", 633 + 592 + 664),
+            ("*$:<=1:sum", 617 + 664),
+            ("any:2..=3:product", 467835),
+            ("*#:0:sum", 0),
+        ];
+
+        for (spec, expected) in table {
+            let rule: Rule = spec.parse().unwrap();
+            assert_eq!(rule.apply(&e), Ok(expected), "{spec}");
+        }
+
+        assert_eq!("*:2:product".parse(), Ok(Rule::gear()));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!("*:2".parse::<Rule>().is_err());
+        assert!("*:two:sum".parse::<Rule>().is_err());
+        assert!("*:2:mean".parse::<Rule>().is_err());
+        assert_eq!(
+            "*:3..=2:sum".parse::<Rule>(),
+            Err("empty part count: 3..=2".to_string())
+        );
+        assert!(":*:2:sum".parse::<Rule>().is_err());
+    }
+}
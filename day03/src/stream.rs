@@ -0,0 +1,205 @@
+use std::{collections::VecDeque, io, io::BufRead};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Event {
+    /// A number next to a symbol, counted by part I.
+    Part(u32),
+    /// A `*` next to exactly two numbers, with their product, for part II.
+    Gear(u64),
+}
+
+struct Row {
+    cells: Vec<char>,
+    /// `(start, end, number)` for each number, `end` exclusive.
+    numbers: Vec<(usize, usize, u32)>,
+}
+
+impl Row {
+    fn new(line: &str) -> Result<Self, String> {
+        let cells: Vec<char> = line.chars().collect();
+        let mut numbers = vec![];
+        let mut x = 0;
+
+        while x < cells.len() {
+            if cells[x].is_ascii_digit() {
+                let start = x;
+                while x < cells.len() && cells[x].is_ascii_digit() {
+                    x += 1;
+                }
+                numbers.push((start, x, crate::number(&cells[start..x])?));
+            } else {
+                x += 1;
+            }
+        }
+
+        Ok(Row { cells, numbers })
+    }
+
+    fn is_symbol(c: char) -> bool {
+        c != '.' && !c.is_ascii_digit()
+    }
+
+    /// Whether there's a symbol in columns `from..to`.
+    fn has_symbol(&self, from: usize, to: usize) -> bool {
+        self.cells
+            .get(from..to.min(self.cells.len()))
+            .is_some_and(|cells| cells.iter().any(|c| Row::is_symbol(*c)))
+    }
+
+    /// Numbers with a digit in column `x - 1`, `x` or `x + 1`.
+    fn numbers_around(&self, x: usize) -> impl Iterator<Item = u32> + '_ {
+        self.numbers
+            .iter()
+            .filter(move |(start, end, _)| *start <= x + 1 && x <= *end)
+            .map(|(_, _, n)| *n)
+    }
+}
+
+/// Works through a schematic one line at a time, holding only the rows
+/// above and below the one being decided. Every event for a row is known as
+/// soon as the row after it has been read.
+pub struct Stream<R> {
+    lines: io::Lines<R>,
+    above: Option<Row>,
+    current: Option<Row>,
+    pending: VecDeque<Event>,
+    done: bool,
+}
+
+impl<R: BufRead> Stream<R> {
+    pub fn new(reader: R) -> Self {
+        Stream {
+            lines: reader.lines(),
+            above: None,
+            current: None,
+            pending: VecDeque::new(),
+            done: false,
+        }
+    }
+
+    fn decide(&mut self, below: Option<&Row>) {
+        let Some(row) = &self.current else {
+            return;
+        };
+        let window = [self.above.as_ref(), Some(row), below];
+
+        for (start, end, n) in &row.numbers {
+            let from = start.saturating_sub(1);
+            if window.iter().flatten().any(|r| r.has_symbol(from, end + 1)) {
+                self.pending.push_back(Event::Part(*n));
+            }
+        }
+
+        for (x, c) in row.cells.iter().enumerate() {
+            if *c != '*' {
+                continue;
+            }
+            let mut around = window.iter().flatten().flat_map(|r| r.numbers_around(x));
+            if let (Some(a), Some(b), None) = (around.next(), around.next(), around.next()) {
+                self.pending.push_back(Event::Gear(a as u64 * b as u64));
+            }
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for Stream<R> {
+    type Item = io::Result<Event>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(event) = self.pending.pop_front() {
+                return Some(Ok(event));
+            }
+            if self.done {
+                return None;
+            }
+
+            let below = match self.lines.next().map(|line| {
+                line.and_then(|line| {
+                    Row::new(&line).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+                })
+            }) {
+                Some(Ok(row)) => Some(row),
+                Some(Err(e)) => {
+                    self.done = true;
+                    return Some(Err(e));
+                }
+                None => {
+                    self.done = true;
+                    None
+                }
+            };
+
+            self.decide(below.as_ref());
+            self.above = self.current.take();
+            self.current = below;
+        }
+    }
+}
+
+/// Part I from the stream, as a check on the engine.
+pub fn part1(input: &str) -> Result<u64, String> {
+    let mut sum = 0;
+    for event in Stream::new(input.as_bytes()) {
+        if let Event::Part(n) = event.map_err(|e| e.to_string())? {
+            sum += n as u64;
+        }
+    }
+    Ok(sum)
+}
+
+/// Part II from the stream.
+pub fn part2(input: &str) -> Result<u128, String> {
+    let mut sum = 0;
+    for event in Stream::new(input.as_bytes()) {
+        if let Event::Gear(n) = event.map_err(|e| e.to_string())? {
+            sum += n as u128;
+        }
+    }
+    Ok(sum)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Event, Stream};
+    use crate::{generator::random_schematic, part1, part2, test::TEST_INPUT};
+
+    type Totals = (Result<u64, String>, Result<u128, String>);
+
+    fn totals(input: &str) -> Totals {
+        (super::part1(input), super::part2(input))
+    }
+
+    #[test]
+    fn test_stream() {
+        let events: Vec<_> = Stream::new(TEST_INPUT.as_bytes())
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(
+            events[..4],
+            [
+                Event::Part(467),
+                Event::Gear(467 * 35),
+                Event::Part(35),
+                Event::Part(633)
+            ]
+        );
+
+        assert_eq!(totals(TEST_INPUT), (Ok(4361), Ok(467835)));
+    }
+
+    #[test]
+    fn test_stream_matches_engine() {
+        for seed in 0..5 {
+            let input = random_schematic(seed, 60, 40);
+            assert_eq!(totals(&input), (part1(&input), part2(&input)));
+        }
+
+        for input in [
+            include_str!("../tests/fixtures/big_numbers.txt"),
+            include_str!("../tests/fixtures/long_number.txt"),
+        ] {
+            assert_eq!(totals(input), (part1(input), part2(input)));
+        }
+    }
+}
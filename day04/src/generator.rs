@@ -0,0 +1,74 @@
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+
+/// How often cards win.
+#[derive(Clone, Debug)]
+pub struct Params {
+    /// Percent chance each winning number is also on the card.
+    pub wins: u32,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params { wins: 20 }
+    }
+}
+
+/// Ten winning numbers and twenty-five on the card, all from 1 to 99 like
+/// the puzzle input. Cards never win copies of cards past the end of the
+/// deck, as the puzzle promises.
+fn card(rng: &mut impl Rng, id: usize, remaining: usize, params: &Params) -> String {
+    let mut pool: Vec<u32> = (1..100).collect();
+    pool.shuffle(rng);
+    let (winning, rest) = pool.split_at(10);
+
+    let mut matches = winning
+        .iter()
+        .filter(|_| rng.gen_range(0..100) < params.wins)
+        .copied()
+        .collect::<Vec<_>>();
+    matches.truncate(remaining);
+
+    let mut have = [&matches[..], &rest[..25 - matches.len()]].concat();
+    have.shuffle(rng);
+
+    let numbers = |ns: &[u32]| {
+        ns.iter()
+            .map(|n| format!("{n:>2}"))
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+    format!("Card {id:>3}: {} | {}", numbers(winning), numbers(&have))
+}
+
+/// A deck of `size` cards; the same seed always gives the same deck.
+pub fn generate(seed: u64, size: usize, params: &Params) -> String {
+    let mut rng = StdRng::seed_from_u64(seed);
+    (0..size)
+        .map(|i| card(&mut rng, i + 1, size - 1 - i, params) + "\n")
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{generate, Params};
+    use crate::parse_scratchcards;
+
+    #[test]
+    fn test_generate() {
+        let input = generate(11, 100, &Params::default());
+        assert_eq!(input, generate(11, 100, &Params::default()));
+
+        let (_, deck) = parse_scratchcards(&input).unwrap();
+        assert_eq!(deck.len(), 100);
+        assert!(deck.iter().zip(1..).all(|(s, id)| s.id == id));
+        assert!(deck.iter().all(|s| s.numbers.len() == 25));
+        assert!(deck
+            .iter()
+            .enumerate()
+            .all(|(i, s)| s.matches().len() < 100 - i));
+
+        let losing = generate(11, 100, &Params { wins: 0 });
+        assert_eq!(crate::part1(&losing), Ok(0));
+        assert_eq!(crate::part2(&losing), Ok(100));
+    }
+}
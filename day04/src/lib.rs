@@ -0,0 +1,133 @@
+pub mod generator;
+pub mod parser;
+
+pub use parser::{parse_scratchcards, Scratchcard};
+
+pub static INPUT: &str = include_str!("../input.txt");
+
+impl Scratchcard {
+    pub fn matches(&self) -> Vec<&u32> {
+        self.numbers.intersection(&self.winning_numbers).collect()
+    }
+}
+
+/// The cards in `input`, or an error showing the line it stopped making
+/// sense on.
+pub fn parse(input: &str) -> Result<Vec<Scratchcard>, String> {
+    let (_, scratchcards) = parse_scratchcards(input)
+        .map_err(|e| e.map_input(|i| i.lines().next().unwrap_or("")).to_string())?;
+    Ok(scratchcards)
+}
+
+/// A point for a card's first match, doubled for each match after it.
+fn points(matches: usize) -> Option<u64> {
+    match matches {
+        0 => Some(0),
+        m => 1u64.checked_shl(u32::try_from(m - 1).ok()?),
+    }
+}
+
+pub fn part1(input: &str) -> Result<u64, String> {
+    parse(input)?
+        .iter()
+        .try_fold(0u64, |sum, s| sum.checked_add(points(s.matches().len())?))
+        .ok_or_else(|| "more points than a u64 holds".to_string())
+}
+
+/// Each card wins one copy of the next cards for each copy of it there is.
+/// The puzzle promises no card wins past the last one, so any that would
+/// are dropped. The copies can double with each card, so even a `u128`
+/// can run out.
+pub fn part2(input: &str) -> Result<u128, String> {
+    let scratchcards = parse(input)?;
+    let too_many = || "more cards than a u128 can count".to_string();
+
+    let mut cards = vec![1u128; scratchcards.len()];
+    for (i, s) in scratchcards.iter().enumerate() {
+        let copies = cards[i];
+        let last = (i + s.matches().len()).min(cards.len() - 1);
+        for card in &mut cards[i + 1..=last] {
+            *card = card.checked_add(copies).ok_or_else(too_many)?;
+        }
+    }
+
+    cards
+        .iter()
+        .try_fold(0u128, |sum, n| sum.checked_add(*n))
+        .ok_or_else(too_many)
+}
+
+#[cfg(test)]
+mod test {
+    use proptest::prelude::*;
+
+    harness::fixtures!(crate::part1, crate::part2);
+
+    /// A card's winning numbers and the numbers on it, with `matches` of
+    /// them in common.
+    fn card(max_matches: usize) -> impl Strategy<Value = (Vec<u32>, Vec<u32>)> {
+        (
+            prop::sample::subsequence((1..100).collect::<Vec<u32>>(), 10),
+            0..=max_matches,
+            1..=5usize,
+        )
+            .prop_map(|(numbers, matches, others)| {
+                let (winning, rest) = numbers.split_at(5);
+                let have = [&winning[..matches], &rest[..others]].concat();
+                (winning.to_vec(), have)
+            })
+    }
+
+    /// Decks that never win copies of cards past the end, as the puzzle
+    /// promises.
+    fn deck() -> impl Strategy<Value = Vec<(Vec<u32>, Vec<u32>)>> {
+        (1..12usize).prop_flat_map(|n| (0..n).map(|i| card(5.min(n - 1 - i))).collect::<Vec<_>>())
+    }
+
+    fn text(deck: &[(Vec<u32>, Vec<u32>)]) -> String {
+        let numbers = |ns: &[u32]| {
+            ns.iter()
+                .map(|n| format!("{n:>2}"))
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+        deck.iter()
+            .enumerate()
+            .map(|(i, (winning, have))| {
+                format!(
+                    "Card {:>3}: {} | {}",
+                    i + 1,
+                    numbers(winning),
+                    numbers(have)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Plays the cards one at a time, adding every copy won to the pile.
+    fn simulate(matches: &[usize]) -> u128 {
+        let mut pile: Vec<usize> = (0..matches.len()).collect();
+        let mut played = 0;
+        while let Some(i) = pile.pop() {
+            played += 1;
+            pile.extend(i + 1..i + 1 + matches[i]);
+        }
+        played
+    }
+
+    proptest! {
+        #[test]
+        fn test_against_simulation(deck in deck()) {
+            let matches: Vec<usize> = deck
+                .iter()
+                .map(|(winning, have)| have.iter().filter(|n| winning.contains(n)).count())
+                .collect();
+            let input = text(&deck);
+
+            let points: u64 = matches.iter().map(|m| if *m == 0 { 0 } else { 1 << (m - 1) }).sum();
+            prop_assert_eq!(crate::part1(&input), Ok(points));
+            prop_assert_eq!(crate::part2(&input), Ok(simulate(&matches)));
+        }
+    }
+}
@@ -0,0 +1,140 @@
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+
+static CATEGORIES: [&str; 8] = [
+    "seed",
+    "soil",
+    "fertilizer",
+    "water",
+    "light",
+    "temperature",
+    "humidity",
+    "location",
+];
+
+/// The shape of an almanac. The defaults give seven maps like the puzzle
+/// input, with smaller numbers.
+#[derive(Clone, Debug)]
+pub struct Params {
+    /// Maps between the seeds and the locations.
+    pub maps: usize,
+    /// Seed ranges, written as start and length pairs.
+    pub seeds: usize,
+    /// Widest a range can be, and widest the gap before one.
+    pub width: u64,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params {
+            maps: 7,
+            seeds: 10,
+            width: 1_000_000,
+        }
+    }
+}
+
+fn category(i: usize) -> String {
+    CATEGORIES
+        .get(i)
+        .map_or_else(|| format!("category{i}"), |c| c.to_string())
+}
+
+/// `size` lines with source ranges that don't overlap, in a random order,
+/// sending them anywhere in `0..span`.
+fn map(rng: &mut impl Rng, size: usize, span: u64, params: &Params) -> Vec<String> {
+    let width = params.width.max(1);
+    let mut source = 0;
+    let mut lines: Vec<String> = (0..size)
+        .map(|_| {
+            source += rng.gen_range(0..width);
+            let length = rng.gen_range(1..=width);
+            let line = format!("{} {source} {length}", rng.gen_range(0..span));
+            source += length;
+            line
+        })
+        .collect();
+    lines.shuffle(rng);
+    lines
+}
+
+/// An almanac with `size` lines in each map; the same seed always gives
+/// the same almanac. Every range starts below `2 * width * size` and is at
+/// most `width` long, so it's an error if those don't fit in a `u64`.
+pub fn generate(seed: u64, size: usize, params: &Params) -> Result<String, String> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let width = params.width.max(1);
+    let span = u64::try_from(size.max(1))
+        .ok()
+        .and_then(|size| width.checked_mul(2)?.checked_mul(size))
+        .filter(|span| span.checked_add(width).is_some())
+        .ok_or_else(|| format!("{size} lines up to {width} wide won't fit in a u64"))?;
+
+    let seeds: Vec<String> = (0..params.seeds.max(1))
+        .map(|_| {
+            let start = rng.gen_range(0..span);
+            format!("{start} {}", rng.gen_range(1..=params.width.max(1)))
+        })
+        .collect();
+    let mut out = format!("seeds: {}\n", seeds.join(" "));
+
+    for i in 0..params.maps.max(1) {
+        out += &format!("\n{}-to-{} map:\n", category(i), category(i + 1));
+        for line in map(&mut rng, size.max(1), span, params) {
+            out += &line;
+            out.push('\n');
+        }
+    }
+
+    Ok(out)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{generate, Params};
+    use crate::parse_maps;
+
+    #[test]
+    fn test_generate() {
+        let input = generate(9, 30, &Params::default()).unwrap();
+        assert_eq!(Ok(input.clone()), generate(9, 30, &Params::default()));
+        assert!(input.contains("\nhumidity-to-location map:\n"));
+
+        let (_, (seeds, maps)) = parse_maps(&input).unwrap();
+        assert_eq!(seeds.len(), 20);
+        assert_eq!(maps.len(), 7);
+        for map in &maps {
+            assert_eq!(map.mappings.len(), 30);
+            assert!(map
+                .mappings
+                .windows(2)
+                .all(|w| w[0].from.end <= w[1].from.start));
+        }
+
+        let params = Params {
+            maps: 12,
+            seeds: 3,
+            width: 10,
+        };
+        let (_, (seeds, maps)) = parse_maps(&generate(9, 5, &params).unwrap()).unwrap();
+        assert_eq!((seeds.len(), maps.len()), (6, 12));
+        assert!(maps
+            .iter()
+            .flat_map(|m| &m.mappings)
+            .all(|m| m.from.end - m.from.start <= 10));
+
+        let wide = Params {
+            width: u64::MAX / 2,
+            ..Params::default()
+        };
+        assert!(generate(9, 1, &wide).is_err());
+        assert!(generate(
+            9,
+            1,
+            &Params {
+                width: 1 << 30,
+                ..wide
+            }
+        )
+        .is_ok());
+    }
+}
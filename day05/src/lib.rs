@@ -0,0 +1,247 @@
+pub mod generator;
+pub mod parser;
+
+use std::ops::Range;
+
+pub use parser::{parse_maps, Map, Mapping};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+pub static INPUT: &str = include_str!("../input.txt");
+
+#[allow(dead_code)]
+fn range_intersection<'a, T: Copy + Ord>(a: &'a Range<T>, b: &'a Range<T>) -> Option<Range<T>> {
+    if b.end < a.start || a.end < b.start {
+        return None;
+    }
+
+    Some(b.start.max(a.start)..a.end.min(b.end))
+}
+
+impl Mapping {
+    fn convert(&self, n: u64) -> u64 {
+        n.wrapping_add(self.delta)
+    }
+
+    #[allow(dead_code)]
+    fn intersection(&self, other: &Range<u64>) -> Option<Mapping> {
+        range_intersection(&self.from, other).map(|range| Mapping {
+            from: range,
+            delta: self.delta,
+        })
+    }
+}
+
+impl Map {
+    fn find_mapping(&self, n: u64) -> Option<&Mapping> {
+        self.mappings
+            .iter()
+            .find(|&mapping| mapping.from.contains(&n))
+    }
+
+    #[allow(dead_code)]
+    fn reduce_mappings(&self, ns: &Range<u64>) -> Map {
+        Map {
+            name: None,
+            mappings: self
+                .mappings
+                .iter()
+                .filter_map(|mapping| mapping.intersection(ns))
+                .collect(),
+        }
+    }
+}
+
+pub fn location(maps: &[Map], seed: u64) -> u64 {
+    maps.iter().fold(seed, |acc, e| {
+        e.find_mapping(acc).map(|m| m.convert(acc)).unwrap_or(acc)
+    })
+}
+
+/// The seeds and maps in `input`, or an error showing the line it stopped
+/// making sense on.
+pub fn parse(input: &str) -> Result<(Vec<u64>, Vec<Map>), String> {
+    let (_, almanac) = parse_maps(input)
+        .map_err(|e| e.map_input(|i| i.lines().next().unwrap_or("")).to_string())?;
+    Ok(almanac)
+}
+
+pub fn part1(input: &str) -> Result<u64, String> {
+    let (seeds, maps) = parse(input)?;
+
+    seeds
+        .iter()
+        .map(|seed| location(&maps, *seed))
+        .reduce(|acc, e| acc.min(e))
+        .ok_or_else(|| "no seeds".to_string())
+}
+
+#[cfg(feature = "parallel")]
+pub fn par_part1(input: &str) -> Result<u64, String> {
+    let (seeds, maps) = parse(input)?;
+
+    seeds
+        .par_iter()
+        .map(|seed| location(&maps, *seed))
+        .min()
+        .ok_or_else(|| "no seeds".to_string())
+}
+
+#[allow(dead_code)]
+fn seed_ranges(seeds: &[u64]) -> Vec<Range<u64>> {
+    seeds.chunks(2).map(|c| c[0]..c[0] + c[1]).collect()
+}
+
+/// Not solved yet, see `seed_ranges` and `Map::reduce_mappings`.
+pub fn part2(_input: &str) -> Option<u64> {
+    None
+}
+
+#[cfg(test)]
+mod test {
+    use proptest::prelude::*;
+
+    use crate::parse_maps;
+
+    harness::fixtures!(crate::part1, crate::part2);
+
+    /// `(destination, source, length)` for each line of a map.
+    type Lines = Vec<(u64, u64, u64)>;
+
+    /// A map's lines, with source ranges that don't overlap, in a random
+    /// order.
+    fn map() -> impl Strategy<Value = Lines> {
+        prop::collection::vec((0..20u64, 1..20u64, 0..200u64), 1..5)
+            .prop_map(|ranges| {
+                let mut source = 0;
+                ranges
+                    .into_iter()
+                    .map(|(gap, length, destination)| {
+                        source += gap;
+                        let line = (destination, source, length);
+                        source += length;
+                        line
+                    })
+                    .collect::<Lines>()
+            })
+            .prop_shuffle()
+    }
+
+    fn almanac() -> impl Strategy<Value = (Vec<u64>, Vec<Lines>)> {
+        (
+            prop::collection::vec(0..200u64, 1..8),
+            prop::collection::vec(map(), 1..5),
+        )
+    }
+
+    fn text(seeds: &[u64], maps: &[Lines]) -> String {
+        let seeds: Vec<String> = seeds.iter().map(u64::to_string).collect();
+        let mut out = format!("seeds: {}\n", seeds.join(" "));
+        for (i, map) in maps.iter().enumerate() {
+            out += &format!("\n{i}-to-{} map:\n", i + 1);
+            for (destination, source, length) in map {
+                out += &format!("{destination} {source} {length}\n");
+            }
+        }
+        out
+    }
+
+    /// Follows one seed through the lines exactly as the puzzle describes.
+    fn brute_force(seed: u64, maps: &[Lines]) -> u64 {
+        maps.iter().fold(seed, |n, map| {
+            map.iter()
+                .find(|(_, source, length)| (*source..source + length).contains(&n))
+                .map_or(n, |(destination, source, _)| n - source + destination)
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn test_part1_brute_force((seeds, maps) in almanac()) {
+            let expected = seeds.iter().map(|seed| brute_force(*seed, &maps)).min();
+            prop_assert_eq!(crate::part1(&text(&seeds, &maps)).ok(), expected);
+        }
+
+        #[test]
+        fn test_reduce_mappings_per_seed(
+            (seeds, maps) in almanac(),
+            start in 0..150u64,
+            length in 0..100u64,
+        ) {
+            let (_, (_, parsed)) = parse_maps(&text(&seeds, &maps)).unwrap();
+            let range = start..start + length;
+            let reduced = parsed[0].reduce_mappings(&range);
+
+            for mapping in &reduced.mappings {
+                prop_assert!(range.start <= mapping.from.start && mapping.from.end <= range.end);
+            }
+            for seed in range.clone() {
+                let within = reduced.mappings.iter().find(|m| m.from.contains(&seed));
+                prop_assert_eq!(
+                    within.map(|m| m.convert(seed)),
+                    parsed[0].find_mapping(seed).map(|m| m.convert(seed)),
+                    "seed {}", seed
+                );
+            }
+        }
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_par_part1() {
+        let example = include_str!("../tests/fixtures/example.txt");
+        assert_eq!(crate::par_part1(example), crate::part1(example));
+        assert_eq!(crate::par_part1(crate::INPUT), crate::part1(crate::INPUT));
+    }
+
+    #[test]
+    fn test_parse_range_overflow() {
+        let almanac = "seeds: 5\n\na-to-b map:\n0 18446744073709551615 2";
+        assert!(parse_maps(almanac).is_err());
+
+        let almanac = "seeds: 18446744073709551614\n\na-to-b map:\n0 18446744073709551614 1";
+        assert_eq!(crate::part1(almanac), Ok(0));
+
+        // The destination range can't run past `u64::MAX` either.
+        let almanac = "seeds: 3\n\na-to-b map:\n18446744073709551615 0 5\n";
+        assert!(parse_maps(almanac).is_err());
+
+        let almanac = "seeds: 0\n\na-to-b map:\n18446744073709551614 0 1";
+        assert_eq!(crate::part1(almanac), Ok(18446744073709551614));
+    }
+
+    #[test]
+    fn test_range_intersection() {
+        assert_eq!(crate::range_intersection(&(0..10), &(20..30)), None);
+        assert_eq!(crate::range_intersection(&(20..30), &(0..10)), None);
+
+        assert_eq!(crate::range_intersection(&(0..30), &(10..20)), Some(10..20));
+        assert_eq!(crate::range_intersection(&(0..20), &(10..30)), Some(10..20));
+        assert_eq!(crate::range_intersection(&(10..30), &(0..20)), Some(10..20));
+    }
+
+    #[test]
+    fn test_map_reduce_mappings() {
+        let m = crate::Map {
+            name: Some("Cool".to_string()),
+            mappings: vec![
+                crate::Mapping {
+                    from: 0..10,
+                    delta: 100,
+                },
+                crate::Mapping {
+                    from: 20..40,
+                    delta: 100,
+                },
+            ],
+        };
+
+        let ranges: Vec<_> = m
+            .reduce_mappings(&(5..30))
+            .mappings
+            .into_iter()
+            .map(|mapping| mapping.from)
+            .collect();
+        assert_eq!(ranges, vec![5..10, 20..30]);
+    }
+}
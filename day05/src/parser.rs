@@ -3,7 +3,7 @@ use std::ops::Range;
 use nom::{
     bytes::complete::{tag, take_until},
     character::complete::{self as cc, space1},
-    combinator::map_res,
+    combinator::{map_opt, map_res},
     multi::separated_list1,
     sequence::tuple,
     IResult,
@@ -12,11 +12,14 @@ use nom::{
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Mapping {
     pub from: Range<u64>,
+    /// `to - from`, wrapping, so adding it wraps back round for maps that
+    /// go down.
     pub delta: u64,
 }
 
 #[derive(Debug)]
 pub struct Map {
+    #[allow(dead_code)]
     pub name: Option<String>,
     pub mappings: Vec<Mapping>,
 }
@@ -25,23 +28,25 @@ fn num(i: &str) -> IResult<&str, u64> {
     map_res(cc::digit1, str::parse)(i)
 }
 
+/// Fails on a source or destination range that runs past `u64::MAX`.
 fn parse_mapping(i: &str) -> IResult<&str, Mapping> {
-    let (i, (to, _, from, _, length)) = tuple((num, space1, num, space1, num))(i)?;
-
-    Ok((
-        i,
-        Mapping {
-            from: from..from + length,
-            delta: to - from,
+    map_opt(
+        tuple((num, space1, num, space1, num)),
+        |(to, _, from, _, length)| {
+            to.checked_add(length)?;
+            Some(Mapping {
+                from: from..from.checked_add(length)?,
+                delta: to.wrapping_sub(from),
+            })
         },
-    ))
+    )(i)
 }
 
 fn mappings(i: &str) -> IResult<&str, Vec<Mapping>> {
     let (i, mappings) = separated_list1(tag("\n"), parse_mapping)(i)?;
 
     let mut new_mappings = mappings.clone();
-    new_mappings.sort_by(|a, b| a.from.start.cmp(&b.from.start));
+    new_mappings.sort_by_key(|m| m.from.start);
 
     Ok((i, new_mappings))
 }
@@ -0,0 +1,163 @@
+pub mod parser;
+
+pub use parser::parse_sheet;
+
+pub static INPUT: &str = include_str!("../input.txt");
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct Race {
+    pub time: u64,
+    pub distance: u64,
+}
+
+impl Race {
+    /// Whether holding the button for `hold` milliseconds beats the record.
+    pub fn wins(&self, hold: u64) -> bool {
+        hold <= self.time && hold as u128 * (self.time - hold) as u128 > self.distance as u128
+    }
+
+    /// The number of ways to beat the record. The winning holds are the
+    /// integers strictly between the roots of `h * (time - h) = distance`,
+    /// so the integer square root gets within a step of the first one and
+    /// the rest is symmetric about `time / 2`.
+    pub fn ways(&self) -> u64 {
+        let (t, d) = (self.time as u128, self.distance as u128);
+        if t * t <= 4 * d {
+            return 0;
+        }
+
+        let root = (t * t - 4 * d).isqrt() as u64;
+        let mut first = (self.time - root) / 2;
+        while first > 0 && self.wins(first - 1) {
+            first -= 1;
+        }
+        while first <= self.time / 2 && !self.wins(first) {
+            first += 1;
+        }
+
+        match self.time - first {
+            last if last >= first => last - first + 1,
+            _ => 0,
+        }
+    }
+}
+
+/// The times and distances of the sheet, or an error showing the line it
+/// stopped making sense on.
+fn sheet(input: &str) -> Result<(Vec<&str>, Vec<&str>), String> {
+    let (_, sheet) = parse_sheet(input)
+        .map_err(|e| e.map_input(|i| i.lines().next().unwrap_or("")).to_string())?;
+    Ok(sheet)
+}
+
+/// A column of digits as a number. The parser has checked every row fits
+/// in a `u64` even joined up, so this only fails on text it didn't parse.
+fn number(digits: &str) -> Result<u64, String> {
+    digits
+        .parse()
+        .map_err(|_| format!("{digits} isn't a number"))
+}
+
+/// Each column as its own race.
+pub fn races(input: &str) -> Result<Vec<Race>, String> {
+    let (times, distances) = sheet(input)?;
+
+    times
+        .iter()
+        .zip(distances)
+        .map(|(time, distance)| {
+            Ok(Race {
+                time: number(time)?,
+                distance: number(distance)?,
+            })
+        })
+        .collect()
+}
+
+/// The columns joined into one race, ignoring the spaces between them.
+pub fn kerned(input: &str) -> Result<Race, String> {
+    let (times, distances) = sheet(input)?;
+
+    Ok(Race {
+        time: number(&times.concat())?,
+        distance: number(&distances.concat())?,
+    })
+}
+
+/// A race never has more ways than its time, and the product of the times is
+/// at most the times joined into one number, which the parser has checked
+/// fits in a `u64`. So the product can't overflow.
+pub fn part1(input: &str) -> Result<u64, String> {
+    Ok(races(input)?.iter().map(Race::ways).product())
+}
+
+pub fn part2(input: &str) -> Result<u64, String> {
+    Ok(kerned(input)?.ways())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{kerned, parse_sheet, races, Race};
+
+    harness::fixtures!(crate::part1, crate::part2);
+
+    #[test]
+    fn test_parse() {
+        let input = include_str!("../tests/fixtures/example.txt");
+
+        assert_eq!(
+            races(input).unwrap()[2],
+            Race {
+                time: 30,
+                distance: 200
+            }
+        );
+        assert_eq!(
+            kerned(input),
+            Ok(Race {
+                time: 71530,
+                distance: 940200
+            })
+        );
+    }
+
+    #[test]
+    fn test_ways() {
+        for time in 0..60 {
+            for distance in 0..time * time / 4 + 2 {
+                let race = Race { time, distance };
+                let brute = (0..=time).filter(|h| race.wins(*h)).count() as u64;
+                assert_eq!(race.ways(), brute, "{race:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_ways_large() {
+        // Close enough to perfect squares that rounding a float root would
+        // be off by one.
+        let race = Race {
+            time: 94_000_000,
+            distance: 47_000_000 * 47_000_000 - 1,
+        };
+        assert_eq!(race.ways(), 1);
+
+        let race = Race {
+            time: 4_000_000_001,
+            distance: 2_000_000_000 * 2_000_000_001 - 1,
+        };
+        assert_eq!(race.ways(), 2);
+
+        let race = Race {
+            time: u64::MAX,
+            distance: 0,
+        };
+        assert_eq!(race.ways(), u64::MAX - 1);
+    }
+
+    #[test]
+    fn test_parse_overflow() {
+        assert!(parse_sheet("Time: 99999999999 99999999999\nDistance: 1 1").is_err());
+        assert!(parse_sheet("Time: 7\nDistance: 99999999999999999999").is_err());
+    }
+}
@@ -0,0 +1,12 @@
+use day06::{part1, part2, INPUT};
+
+fn main() {
+    match part1(INPUT) {
+        Ok(answer) => println!("Day 06, part I:  {}", answer),
+        Err(e) => println!("Day 06, part I:  error: {}", e),
+    }
+    match part2(INPUT) {
+        Ok(answer) => println!("Day 06, part II: {}", answer),
+        Err(e) => println!("Day 06, part II: error: {}", e),
+    }
+}
@@ -0,0 +1,24 @@
+use nom::{
+    bytes::complete::tag,
+    character::complete::{digit1, line_ending, space1},
+    combinator::verify,
+    multi::separated_list1,
+    sequence::{preceded, separated_pair, tuple},
+    IResult,
+};
+
+/// The digits of each column of a row, left as text so they can be read
+/// either as separate numbers or joined into one. Fails if the joined
+/// number doesn't fit in a `u64`, as then neither reading does.
+fn row<'a>(name: &'static str) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<&'a str>> {
+    preceded(
+        tuple((tag(name), tag(":"), space1)),
+        verify(separated_list1(space1, digit1), |columns: &[&str]| {
+            columns.concat().parse::<u64>().is_ok()
+        }),
+    )
+}
+
+pub fn parse_sheet(i: &str) -> IResult<&str, (Vec<&str>, Vec<&str>)> {
+    separated_pair(row("Time"), line_ending, row("Distance"))(i)
+}
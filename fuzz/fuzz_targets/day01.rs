@@ -0,0 +1,10 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+// Every line is valid input, so every part has to give an answer.
+fuzz_target!(|input: &str| {
+    day01::part1(input);
+    day01::part2(input);
+    day01::part2_ends(input);
+});
@@ -0,0 +1,13 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+// Parsing has to give games or an error, and any games it gives have
+// answers, as that's how the runner calls the parts.
+fuzz_target!(|input: &str| {
+    let _ = day02::parse(input).map(|games| {
+        day02::part1(&games);
+        day02::part2(&games);
+        day02::stats(&games);
+    });
+});
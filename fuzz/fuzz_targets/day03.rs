@@ -0,0 +1,12 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+// Any text is a schematic, so every part has to give an answer or an error.
+fuzz_target!(|input: &str| {
+    let _ = day03::Engine::try_load(input);
+    let _ = day03::part1(input);
+    let _ = day03::part2(input);
+    let _ = day03::stream::part1(input);
+    let _ = day03::stream::part2(input);
+});
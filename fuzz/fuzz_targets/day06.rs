@@ -0,0 +1,9 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+// Every part has to give an answer or an error, whatever the input.
+fuzz_target!(|input: &str| {
+    let _ = day06::part1(input);
+    let _ = day06::part2(input);
+});
@@ -0,0 +1,172 @@
+use std::{fs, path::Path};
+
+/// Something a part can return. `None` means the part isn't solved yet, so
+/// there's nothing to check, and an error that the input has no answer the
+/// part can give, like one too big to count.
+pub trait Answer {
+    fn answer(self) -> Result<Option<String>, String>;
+}
+
+impl<T: ToString> Answer for Option<T> {
+    fn answer(self) -> Result<Option<String>, String> {
+        Ok(self.map(|a| a.to_string()))
+    }
+}
+
+impl<T: Answer, E: ToString> Answer for Result<T, E> {
+    fn answer(self) -> Result<Option<String>, String> {
+        self.map_err(|e| e.to_string())?.answer()
+    }
+}
+
+macro_rules! answer {
+    ($($t:ty),*) => {$(
+        impl Answer for $t {
+            fn answer(self) -> Result<Option<String>, String> {
+                Ok(Some(self.to_string()))
+            }
+        }
+    )*};
+}
+
+answer!(u32, u64, u128, usize, i32, i64, String);
+
+/// A part of a day's solution, returning its answer if it has one.
+pub type Part<'a> = &'a dyn Fn(&str) -> Result<Option<String>, String>;
+
+/// Reads an `.expected` file: a `partN: <answer>` line for each part whose
+/// answer is known, or `partN: error: <message>` for a part that should
+/// fail with that error.
+pub fn expected(s: &str) -> Vec<(usize, String)> {
+    s.lines()
+        .filter_map(|line| {
+            let (name, answer) = line.split_once(':')?;
+            let part = name.trim().strip_prefix("part")?.parse().ok()?;
+            Some((part, answer.trim().to_string()))
+        })
+        .collect()
+}
+
+/// Runs `parts` over every `name.txt` in `dir` that has a `name.expected`
+/// beside it, and panics listing every answer that didn't match. Parts
+/// that aren't solved yet are skipped.
+pub fn run(dir: &Path, parts: &[Part]) {
+    let mut names: Vec<String> = fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            let name = path.file_name()?.to_str()?.strip_suffix(".txt")?;
+            dir.join(format!("{name}.expected"))
+                .exists()
+                .then(|| name.to_string())
+        })
+        .collect();
+    names.sort();
+
+    let mut failures = vec![];
+    for name in names {
+        let input = fs::read_to_string(dir.join(format!("{name}.txt"))).unwrap();
+        let answers = fs::read_to_string(dir.join(format!("{name}.expected"))).unwrap();
+
+        for (part, want) in expected(&answers) {
+            let Some(solve) = parts.get(part.wrapping_sub(1)) else {
+                failures.push(format!("{name}: no part {part}"));
+                continue;
+            };
+            let got = match solve(&input) {
+                Ok(Some(got)) => got,
+                Ok(None) => continue,
+                Err(e) => format!("error: {e}"),
+            };
+            if got != want {
+                failures.push(format!("{name}, part {part}: expected {want}, got {got}"));
+            }
+        }
+    }
+
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
+
+/// A test running the given parts, in order, over the crate's
+/// `tests/fixtures`. Each part is anything callable with the input `&str`
+/// that returns an [`Answer`].
+#[macro_export]
+macro_rules! fixtures {
+    ($($part:expr),+ $(,)?) => {
+        #[test]
+        fn fixtures() {
+            $crate::run(
+                &::std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures"),
+                &[$(&|input: &str| $crate::Answer::answer(($part)(input))),+],
+            );
+        }
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use std::{env, fs};
+
+    use super::{expected, run, Answer};
+
+    #[test]
+    fn test_answer() {
+        assert_eq!(3u32.answer(), Ok(Some("3".to_string())));
+        assert_eq!(Some(4u64).answer(), Ok(Some("4".to_string())));
+        assert_eq!(None::<u64>.answer(), Ok(None));
+        assert_eq!(Ok::<_, String>(5u128).answer(), Ok(Some("5".to_string())));
+        assert_eq!(
+            Err::<u32, _>("too big").answer(),
+            Err("too big".to_string())
+        );
+    }
+
+    #[test]
+    fn test_expected() {
+        assert_eq!(
+            expected("part1: 142\npart2:281\nnotes\n"),
+            [(1, "142".to_string()), (2, "281".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_run() {
+        let dir = env::temp_dir().join(format!("harness-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("a.txt"), "1 2 3").unwrap();
+        fs::write(dir.join("a.expected"), "part1: 6\npart2: 3\n").unwrap();
+        fs::write(dir.join("b.txt"), "1 x").unwrap();
+        fs::write(dir.join("b.expected"), "part1: error: not a number: x\n").unwrap();
+        fs::write(dir.join("no_answers.txt"), "ignored").unwrap();
+
+        let sum = |input: &str| {
+            input
+                .split(' ')
+                .map(|n| n.parse::<u32>().map_err(|_| format!("not a number: {n}")))
+                .sum::<Result<u32, _>>()
+                .answer()
+        };
+        let lenient = |input: &str| {
+            input
+                .split(' ')
+                .filter_map(|n| n.parse::<u32>().ok())
+                .sum::<u32>()
+                .answer()
+        };
+        let pending = |_: &str| None::<u32>.answer();
+        let count = |input: &str| input.split(' ').count().answer();
+        let wrong = |_: &str| 7u32.answer();
+
+        run(&dir, &[&sum, &pending]);
+        run(&dir, &[&sum, &count]);
+        let failed = std::panic::catch_unwind(|| run(&dir, &[&sum, &wrong]));
+        let only_one = std::panic::catch_unwind(|| run(&dir, &[&sum]));
+        let no_error = std::panic::catch_unwind(|| run(&dir, &[&lenient, &pending]));
+
+        fs::remove_dir_all(&dir).unwrap();
+        assert!(failed.is_err());
+        assert!(only_one.is_err());
+        assert!(no_error.is_err());
+    }
+}